@@ -0,0 +1,207 @@
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::euclidean::Coordinate;
+use crate::polysplit::{self, PolySplit, CutRatioResult, DistanceToSegmentResult};
+use crate::polysplit::{PolySplitError, PolySplitErrorKind, Result};
+
+/// Metric defines axis weights of the anisotropic distance `sqrt(wx * dx^2 + wy * dy^2)`.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::anisotropic::Metric;
+///
+/// // Moving along y axis is twice as expensive
+/// let stairs = Metric::new(1.0, 4.0).unwrap();
+///
+/// let a = stairs.point(0.0, 0.0);
+/// assert_eq!(a.distance_to(&stairs.point(0.0, 1.0)), 2.0);
+///
+/// assert!(Metric::new(1.0, 0.0).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metric<T = f64> {
+    wx: T,
+    wy: T,
+}
+
+impl<T: Coordinate> Metric<T> {
+    /// Creates metric with weights of squared differences along x and y axes,
+    /// both of them must be finite and positive.
+    pub fn new(wx: T, wy: T) -> Result<Self> {
+        let metric = Metric { wx, wy };
+        if !metric.is_valid() {
+            return Err(PolySplitError{
+                kind: PolySplitErrorKind::InvalidMetric,
+                message: format!("metric weights {:?} and {:?} must be finite and positive", wx, wy),
+            });
+        }
+
+        Ok(metric)
+    }
+
+    /// Returns weight of the squared difference along x axis.
+    pub fn wx(&self) -> T {
+        self.wx
+    }
+
+    /// Returns weight of the squared difference along y axis.
+    pub fn wy(&self) -> T {
+        self.wy
+    }
+
+    /// Creates point measuring distances by the metric.
+    pub fn point(&self, x: T, y: T) -> Point<T> {
+        Point { x, y, metric: *self }
+    }
+
+    /// Splits polyline by points checking that all of them are created with the metric,
+    /// see [polyline_split](crate::polyline_split).
+    ///
+    /// Points carry their metric and [PolySplit] measures distances by the metric of the point
+    /// they are measured from, so mixing metrics gives meaningless cut points. This method returns
+    /// [InvalidMetric](PolySplitErrorKind::InvalidMetric) error for such input instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polysplit::anisotropic::Metric;
+    /// use polysplit::PolySplitErrorKind;
+    ///
+    /// let metric = Metric::new(1.0, 4.0).unwrap();
+    /// let polyline = vec![metric.point(0.0, 0.0), metric.point(10.0, 0.0)];
+    ///
+    /// let segments = metric.polyline_split(&polyline, &[metric.point(1.0, 1.0), metric.point(9.0, 1.0)], None).unwrap();
+    /// assert_eq!(segments[0][0].x, 1.0);
+    ///
+    /// let other = Metric::new(1.0, 1.0).unwrap();
+    /// let error = metric.polyline_split(&polyline, &[other.point(1.0, 1.0)], None).unwrap_err();
+    /// assert_eq!(error.kind(), &PolySplitErrorKind::InvalidMetric);
+    /// ```
+    pub fn polyline_split(
+        &self,
+        polyline: &[Point<T>],
+        points: &[Point<T>],
+        distance_threshold: Option<T>,
+    ) -> Result<Vec<Vec<Point<T>>>> {
+        self.check_points(polyline, points)?;
+        polysplit::polyline_split(polyline, points, distance_threshold)
+    }
+
+    /// Splits polyline by points with geometric tolerance checking that all of them are created
+    /// with the metric, see [polyline_split_with_tolerance](crate::polyline_split_with_tolerance).
+    pub fn polyline_split_with_tolerance(
+        &self,
+        polyline: &[Point<T>],
+        points: &[Point<T>],
+        distance_threshold: Option<T>,
+        tolerance: T,
+    ) -> Result<Vec<Vec<Point<T>>>> {
+        self.check_points(polyline, points)?;
+        polysplit::polyline_split_with_tolerance(polyline, points, distance_threshold, tolerance)
+    }
+
+    fn check_points(&self, polyline: &[Point<T>], points: &[Point<T>]) -> Result<()> {
+        if polyline.iter().chain(points).any(|point| point.metric != *self) {
+            return Err(PolySplitError{
+                kind: PolySplitErrorKind::InvalidMetric,
+                message: format!("all points must have metric {:?}", self),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn is_valid(&self) -> bool {
+        self.wx.is_finite() && self.wy.is_finite() && self.wx > T::zero() && self.wy > T::zero()
+    }
+}
+
+/// Point `(x, y)` measuring distances by the anisotropic [Metric] it was created with.
+///
+/// Distances and projections use the metric of the point they are measured from,
+/// so points of the same split are expected to share the metric, [Metric::polyline_split]
+/// checks it.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point<T = f64> {
+    pub x: T,
+    pub y: T,
+    metric: Metric<T>,
+}
+
+impl<T: Coordinate> Point<T> {
+    pub fn new(x: T, y: T, metric: Metric<T>) -> Self {
+        Point { x, y, metric }
+    }
+
+    pub fn metric(&self) -> Metric<T> {
+        self.metric
+    }
+
+    pub fn distance_to(&self, to: &Point<T>) -> T {
+        let dx = self.x - to.x;
+        let dy = self.y - to.y;
+
+        (self.metric.wx * dx * dx + self.metric.wy * dy * dy).sqrt()
+    }
+}
+
+impl<T: Coordinate> PolySplit<T> for Point<T> {
+    fn distance_to_point(&self, point: &Self) -> T {
+        self.distance_to(point)
+    }
+
+    fn distance_to_segment(&self, s: (&Self, &Self)) -> DistanceToSegmentResult<Self, T> {
+        self.distance_to_segment_with_tolerance(s, T::DEGENERATE_TOLERANCE)
+    }
+
+    fn distance_to_segment_with_tolerance(&self, s: (&Self, &Self), tolerance: T) -> DistanceToSegmentResult<Self, T> {
+        let segment_distance = s.0.distance_to(s.1);
+        if segment_distance <= tolerance {
+            return DistanceToSegmentResult{
+                distance: self.distance_to(s.0),
+                cut_point: *s.0,
+                cut_ratio: CutRatioResult::Begin,
+            };
+        }
+
+        let vx = s.1.x - s.0.x;
+        let vy = s.1.y - s.0.y;
+
+        let ux = self.x - s.0.x;
+        let uy = self.y - s.0.y;
+
+        let (wx, wy) = (self.metric.wx, self.metric.wy);
+        let ratio = (wx*ux*vx+wy*uy*vy)/(wx*vx*vx+wy*vy*vy);
+        let cut_ratio = ratio.max(T::zero()).min(T::one());
+
+        if cut_ratio <= T::zero() {
+            DistanceToSegmentResult{
+                distance: self.distance_to(s.0),
+                cut_point: *s.0,
+                cut_ratio: CutRatioResult::Begin,
+            }
+        } else if cut_ratio >= T::one() {
+            DistanceToSegmentResult{
+                distance: self.distance_to(s.1),
+                cut_point: *s.1,
+                cut_ratio: CutRatioResult::End,
+            }
+        } else {
+            let cut_point = Point::new(s.0.x + cut_ratio * vx, s.0.y + cut_ratio * vy, s.0.metric);
+
+            DistanceToSegmentResult{
+                distance: self.distance_to(&cut_point),
+                cut_point,
+                cut_ratio: CutRatioResult::Medium(cut_ratio.into()),
+            }
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.metric.is_valid()
+    }
+}
@@ -0,0 +1,149 @@
+//! CSV input and output enabled by `csv` feature.
+//!
+//! Polylines and points are read from rows with `id`, `sequence`, `x` and `y` columns
+//! (and optional `z` and `m` ones), grouped by `id` and ordered by `sequence`.
+//! Segments are written one row per vertex with `polyline_id`, `segment_index`,
+//! `vertex_index`, `x` and `y` columns followed by `z` and `m` if the point type has them.
+use alloc::collections::BTreeMap;
+use core::fmt::Debug;
+use std::io::{Read, Write};
+
+use csv::{Reader, StringRecord, Writer};
+
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, with_polyline_id, PolySplit, PolySplitError, PolySplitErrorKind, Result};
+
+/// Reads point groups by `id` ordered by `sequence`.
+pub fn read_groups<P: Ordinates<f64>>(reader: impl Read) -> Result<BTreeMap<String, Vec<P>>> {
+    let mut reader = Reader::from_reader(reader);
+    let headers = reader.headers().map_err(csv_error)?.clone();
+
+    let column = |name: &str| headers.iter().position(|header| header.trim() == name);
+    let required_column = |name: &str| {
+        column(name).ok_or_else(|| invalid_format(&format!("{} column is expected", name)))
+    };
+
+    let (id, sequence) = (required_column("id")?, required_column("sequence")?);
+    let (x, y) = (required_column("x")?, required_column("y")?);
+    let (z, m) = (column("z"), column("m"));
+
+    let mut groups: BTreeMap<String, Vec<(i64, P)>> = BTreeMap::new();
+    for (row_index, record) in reader.records().enumerate() {
+        let record = record.map_err(csv_error)?;
+        let value = |column: usize| read_value(&record, row_index, column);
+        let optional_value = |column: Option<usize>| column.map(value).transpose();
+
+        let point = P::from_ordinates(value(x)?, value(y)?, optional_value(z)?, optional_value(m)?);
+        let sequence = read_value(&record, row_index, sequence)?;
+
+        groups
+            .entry(record.get(id).unwrap_or_default().to_string())
+            .or_default()
+            .push((sequence, point));
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(id, mut points)| {
+            points.sort_by_key(|(sequence, _)| *sequence);
+            (id, points.into_iter().map(|(_, point)| point).collect())
+        })
+        .collect())
+}
+
+/// Writes segments of polylines one row per vertex.
+pub fn write_segments<P: Ordinates<f64>>(writer: impl Write, segments: &BTreeMap<String, Vec<Vec<P>>>) -> Result<()> {
+    let mut writer = Writer::from_writer(writer);
+
+    let mut headers = vec!["polyline_id", "segment_index", "vertex_index", "x", "y"];
+    if P::HAS_Z {
+        headers.push("z");
+    }
+    if P::HAS_M {
+        headers.push("m");
+    }
+    writer.write_record(&headers).map_err(csv_error)?;
+
+    for (id, polyline_segments) in segments {
+        for (segment_index, segment) in polyline_segments.iter().enumerate() {
+            for (vertex_index, point) in segment.iter().enumerate() {
+                let mut record = vec![
+                    id.clone(),
+                    segment_index.to_string(),
+                    vertex_index.to_string(),
+                    point.x().to_string(),
+                    point.y().to_string(),
+                ];
+                record.extend(point.z().map(|z| z.to_string()));
+                record.extend(point.m().map(|m| m.to_string()));
+
+                writer.write_record(&record).map_err(csv_error)?;
+            }
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Splits every polyline by the points with the same `id` and writes the segments.
+/// Polylines without points are skipped and have no rows in the output.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::csv::csv_split;
+/// use polysplit::euclidean::Point;
+///
+/// let polylines = "id,sequence,x,y\na,2,10,0\na,1,0,0\n";
+/// let points = "id,sequence,x,y\na,1,1,1\na,2,9,-1\n";
+///
+/// let mut output = Vec::new();
+/// csv_split::<Point>(polylines.as_bytes(), points.as_bytes(), &mut output, None).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "polyline_id,segment_index,vertex_index,x,y\na,0,0,1,0\na,0,1,9,0\n",
+/// );
+/// ```
+pub fn csv_split<P>(
+    polylines: impl Read,
+    points: impl Read,
+    writer: impl Write,
+    distance_threshold: Option<f64>,
+) -> Result<()>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let polylines: BTreeMap<String, Vec<P>> = read_groups(polylines)?;
+    let points: BTreeMap<String, Vec<P>> = read_groups(points)?;
+
+    let segments = polylines
+        .into_iter()
+        .filter_map(|(id, polyline)| {
+            let points = points.get(&id)?;
+
+            Some(match polyline_split(&polyline, points, distance_threshold) {
+                Ok(segments) => Ok((id, segments)),
+                Err(error) => Err(with_polyline_id(error, &id)),
+            })
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    write_segments(writer, &segments)
+}
+
+fn read_value<T: core::str::FromStr>(record: &StringRecord, row_index: usize, column: usize) -> Result<T> {
+    let value = record.get(column).unwrap_or_default().trim();
+
+    value.parse().map_err(|_| {
+        invalid_format(&format!("invalid value {:?} in column {} of row {}", value, column, row_index + 1))
+    })
+}
+
+fn csv_error(error: csv::Error) -> PolySplitError {
+    PolySplitError{
+        kind: if error.is_io_error() { PolySplitErrorKind::Io } else { PolySplitErrorKind::InvalidFormat },
+        message: error.to_string(),
+    }
+}
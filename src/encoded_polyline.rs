@@ -0,0 +1,158 @@
+//! [Encoded polyline](https://developers.google.com/maps/documentation/utilities/polylinealgorithm)
+//! decoding and encoding enabled by `encoded-polyline` feature.
+//!
+//! Encoded values are latitude and longitude pairs, they are mapped to `y` and `x` of the point
+//! respectively. Precision is the number of decimal digits, `5` is used by Google
+//! and `6` by OSRM and Valhalla.
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use num_traits::Float;
+
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, PolySplit, Result};
+
+/// Decodes encoded polyline into points.
+pub fn decode<P: Ordinates<f64>>(encoded: &str, precision: u32) -> Result<Vec<P>> {
+    let factor = factor(precision);
+    let mut bytes = encoded.bytes().enumerate();
+    let mut points = Vec::new();
+    let (mut latitude, mut longitude) = (0i64, 0i64);
+
+    while bytes.len() > 0 {
+        latitude = latitude
+            .checked_add(decode_value(&mut bytes)?)
+            .ok_or_else(|| invalid_format("latitude overflows"))?;
+        longitude = longitude
+            .checked_add(decode_value(&mut bytes)?)
+            .ok_or_else(|| invalid_format("longitude overflows"))?;
+
+        points.push(P::from_ordinates(
+            longitude as f64 / factor,
+            latitude as f64 / factor,
+            None,
+            None,
+        ));
+    }
+
+    Ok(points)
+}
+
+/// Encodes points into encoded polyline,
+/// coordinates scaled by the precision have to fit into `i64` as well as differences between them.
+pub fn encode<P: Ordinates<f64>>(points: &[P], precision: u32) -> Result<String> {
+    let factor = factor(precision);
+    let mut encoded = String::new();
+    let (mut latitude, mut longitude) = (0i64, 0i64);
+
+    for (index, point) in points.iter().enumerate() {
+        let (next_latitude, next_longitude) = (
+            scale(point.y(), factor).ok_or_else(|| invalid_format(&format!("point {} has invalid latitude", index)))?,
+            scale(point.x(), factor).ok_or_else(|| invalid_format(&format!("point {} has invalid longitude", index)))?,
+        );
+
+        let (delta_latitude, delta_longitude) = (
+            next_latitude
+                .checked_sub(latitude)
+                .ok_or_else(|| invalid_format(&format!("point {} latitude difference overflows", index)))?,
+            next_longitude
+                .checked_sub(longitude)
+                .ok_or_else(|| invalid_format(&format!("point {} longitude difference overflows", index)))?,
+        );
+
+        encode_value(&mut encoded, delta_latitude);
+        encode_value(&mut encoded, delta_longitude);
+        (latitude, longitude) = (next_latitude, next_longitude);
+    }
+
+    Ok(encoded)
+}
+
+/// Splits encoded polyline by the points and returns each segment as encoded polyline
+/// of the same precision.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::euclidean::Point;
+/// use polysplit::encoded_polyline::encoded_polyline_split;
+///
+/// let points = vec![Point(-120.2, 38.5), Point(-120.95, 40.7), Point(-126.453, 43.252)];
+///
+/// let segments = encoded_polyline_split("_p~iF~ps|U_ulLnnqC_mqNvxq`@", &points, None, 5).unwrap();
+///
+/// assert_eq!(segments, vec!["_p~iF~ps|U_ulLnnqC", "_flwFn`faV_mqNvxq`@"]);
+/// ```
+pub fn encoded_polyline_split<P>(
+    encoded: &str,
+    points: &[P],
+    distance_threshold: Option<f64>,
+    precision: u32,
+) -> Result<Vec<String>>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let polyline: Vec<P> = decode(encoded, precision)?;
+    let segments = polyline_split(&polyline, points, distance_threshold)?;
+
+    segments.iter().map(|segment| encode(segment, precision)).collect()
+}
+
+fn factor(precision: u32) -> f64 {
+    (0..precision).fold(1.0, |factor, _| factor * 10.0)
+}
+
+/// Returns the coordinate scaled by the factor and rounded
+/// or `None` if it is not finite or does not fit into `i64`.
+fn scale(value: f64, factor: f64) -> Option<i64> {
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0; // 2^63
+
+    let value = Float::round(value * factor);
+    if value.is_finite() && (-LIMIT..LIMIT).contains(&value) {
+        Some(value as i64)
+    } else {
+        None
+    }
+}
+
+fn decode_value(bytes: &mut impl Iterator<Item = (usize, u8)>) -> Result<i64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+
+    loop {
+        let (index, byte) = bytes
+            .next()
+            .ok_or_else(|| invalid_format("unexpected end of encoded polyline"))?;
+
+        if !(63..127).contains(&byte) {
+            return Err(invalid_format(&format!("invalid character at position {}", index)));
+        }
+
+        // The last of 13 chunks keeps only 4 bits of 64-bit value
+        let chunk = (byte - 63) as u64;
+        if shift > 60 || (chunk & 0x1f) >> (64 - shift).min(5) != 0 {
+            return Err(invalid_format(&format!("value at position {} overflows", index)));
+        }
+
+        value |= (chunk & 0x1f) << shift;
+        shift += 5;
+
+        if chunk < 0x20 {
+            break;
+        }
+    }
+
+    Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+}
+
+fn encode_value(encoded: &mut String, value: i64) {
+    let mut value = ((value << 1) ^ (value >> 63)) as u64;
+
+    while value >= 0x20 {
+        encoded.push((((value & 0x1f) | 0x20) as u8 + 63) as char);
+        value >>= 5;
+    }
+    encoded.push((value as u8 + 63) as char);
+}
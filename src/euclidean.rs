@@ -1,66 +1,603 @@
-use crate::polysplit::{PolySplit, CutRatioResult, DistanceToSegmentResult};
+use core::fmt::Debug;
 
+use num_traits::Float;
+
+use crate::polysplit::{CutRatioResult, DistanceToSegmentResult};
+
+/// Coordinate defines floating point types that can be used by euclidean points.
+pub trait Coordinate: Float + Into<f64> + Debug {
+    /// Segments not longer than this value are considered degenerate
+    /// and projected to their start point, unless other tolerance is given.
+    /// It is a distance, not the machine epsilon of the type.
+    const DEGENERATE_TOLERANCE: Self;
+
+    /// Converts `f64` value, for example a ratio, to the coordinate type.
+    fn from_f64(value: f64) -> Self;
+}
+
+impl Coordinate for f64 {
+    const DEGENERATE_TOLERANCE: f64 = 1e-9;
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+impl Coordinate for f32 {
+    const DEGENERATE_TOLERANCE: f32 = 1e-5;
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+/// Planar point `(x, y)`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Point<T = f64>(pub T, pub T);
+
+/// Point `(x, y, z)` with elevation, distances are planar.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PointZ<T = f64>(pub T, pub T, pub T);
+
+/// Point `(x, y, m)` with measure, distances are planar.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PointM<T = f64>(pub T, pub T, pub T);
+
+/// Point `(x, y, z, m)` with elevation and measure, distances are planar.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct PointZM<T = f64>(pub T, pub T, pub T, pub T);
+
+/// Point `(x, y, z)`, distances are spatial.
 #[derive(Clone, Copy, Debug)]
-pub struct Point(pub f64, pub f64);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Point3D<T = f64>(pub T, pub T, pub T);
+
+/// Point `(x, y, z, m)` with measure, distances are spatial.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Point3DM<T = f64>(pub T, pub T, pub T, pub T);
+
+/// Ordinates gives access to ordinates of point types,
+/// so they can be read from and written to geometry formats.
+pub trait Ordinates<T>: Sized {
+    /// The type has elevation ordinate.
+    const HAS_Z: bool;
+    /// The type has measure ordinate.
+    const HAS_M: bool;
+
+    fn x(&self) -> T;
+    fn y(&self) -> T;
+    /// Returns elevation if the type has it.
+    fn z(&self) -> Option<T>;
+    /// Returns measure if the type has it.
+    fn m(&self) -> Option<T>;
+
+    /// Creates point from ordinates, missing ones the type has are set to zero
+    /// and ones the type does not have are ignored.
+    fn from_ordinates(x: T, y: T, z: Option<T>, m: Option<T>) -> Self;
+}
+
+impl<T: Coordinate> Ordinates<T> for Point<T> {
+    const HAS_Z: bool = false;
+    const HAS_M: bool = false;
 
-impl Point {
-    pub fn distance_to(&self, to: &Point) -> f64 {
-        ((self.0 - to.0).powf(2.0) + (self.1 - to.1).powf(2.0)).sqrt()
+    fn x(&self) -> T {
+        self.0
+    }
+
+    fn y(&self) -> T {
+        self.1
+    }
+
+    fn z(&self) -> Option<T> {
+        None
+    }
+
+    fn m(&self) -> Option<T> {
+        None
+    }
+
+    fn from_ordinates(x: T, y: T, _z: Option<T>, _m: Option<T>) -> Self {
+        Point(x, y)
+    }
+}
+
+impl<T: Coordinate> Ordinates<T> for PointZ<T> {
+    const HAS_Z: bool = true;
+    const HAS_M: bool = false;
+
+    fn x(&self) -> T {
+        self.0
+    }
+
+    fn y(&self) -> T {
+        self.1
+    }
+
+    fn z(&self) -> Option<T> {
+        Some(self.2)
+    }
+
+    fn m(&self) -> Option<T> {
+        None
+    }
+
+    fn from_ordinates(x: T, y: T, z: Option<T>, _m: Option<T>) -> Self {
+        PointZ(x, y, z.unwrap_or_else(T::zero))
     }
 }
 
-impl PolySplit<f64> for Point {
-    fn distance_to_point(&self, point: &Self) -> f64 {
-        return self.distance_to(point);
+impl<T: Coordinate> Ordinates<T> for PointM<T> {
+    const HAS_Z: bool = false;
+    const HAS_M: bool = true;
+
+    fn x(&self) -> T {
+        self.0
     }
 
-    fn distance_to_segment(&self, s: (&Point, &Point)) -> DistanceToSegmentResult<Point, f64> {
-        let segment_distance = s.0.distance_to(s.1);
-        if segment_distance < 1e-9 {
-            let distance = self.distance_to(s.0);
-            return DistanceToSegmentResult{
-                distance,
-                cut_point: *s.0,
-                cut_ratio: CutRatioResult::Begin,
-            };
+    fn y(&self) -> T {
+        self.1
+    }
+
+    fn z(&self) -> Option<T> {
+        None
+    }
+
+    fn m(&self) -> Option<T> {
+        Some(self.2)
+    }
+
+    fn from_ordinates(x: T, y: T, _z: Option<T>, m: Option<T>) -> Self {
+        PointM(x, y, m.unwrap_or_else(T::zero))
+    }
+}
+
+impl<T: Coordinate> Ordinates<T> for PointZM<T> {
+    const HAS_Z: bool = true;
+    const HAS_M: bool = true;
+
+    fn x(&self) -> T {
+        self.0
+    }
+
+    fn y(&self) -> T {
+        self.1
+    }
+
+    fn z(&self) -> Option<T> {
+        Some(self.2)
+    }
+
+    fn m(&self) -> Option<T> {
+        Some(self.3)
+    }
+
+    fn from_ordinates(x: T, y: T, z: Option<T>, m: Option<T>) -> Self {
+        PointZM(x, y, z.unwrap_or_else(T::zero), m.unwrap_or_else(T::zero))
+    }
+}
+
+impl<T: Coordinate> Ordinates<T> for Point3D<T> {
+    const HAS_Z: bool = true;
+    const HAS_M: bool = false;
+
+    fn x(&self) -> T {
+        self.0
+    }
+
+    fn y(&self) -> T {
+        self.1
+    }
+
+    fn z(&self) -> Option<T> {
+        Some(self.2)
+    }
+
+    fn m(&self) -> Option<T> {
+        None
+    }
+
+    fn from_ordinates(x: T, y: T, z: Option<T>, _m: Option<T>) -> Self {
+        Point3D(x, y, z.unwrap_or_else(T::zero))
+    }
+}
+
+impl<T: Coordinate> Ordinates<T> for Point3DM<T> {
+    const HAS_Z: bool = true;
+    const HAS_M: bool = true;
+
+    fn x(&self) -> T {
+        self.0
+    }
+
+    fn y(&self) -> T {
+        self.1
+    }
+
+    fn z(&self) -> Option<T> {
+        Some(self.2)
+    }
+
+    fn m(&self) -> Option<T> {
+        Some(self.3)
+    }
+
+    fn from_ordinates(x: T, y: T, z: Option<T>, m: Option<T>) -> Self {
+        Point3DM(x, y, z.unwrap_or_else(T::zero), m.unwrap_or_else(T::zero))
+    }
+}
+
+/// Euclidean defines how point types are projected and interpolated,
+/// so all of them share the same implementation of [PolySplit].
+pub(crate) trait Euclidean<T: Coordinate>: Copy {
+    /// Returns coordinates used to calculate distances,
+    /// unused ones are zero.
+    fn position(&self) -> [T; 3];
+
+    /// Returns `true` if all ordinates are finite.
+    fn is_finite(&self) -> bool;
+
+    /// Returns the point located at `ratio` between `self` and `to`,
+    /// all ordinates are interpolated linearly.
+    fn interpolate(&self, to: &Self, ratio: T) -> Self;
+}
+
+pub(crate) fn lerp<T: Coordinate>(from: T, to: T, ratio: T) -> T {
+    from + ratio * (to - from)
+}
+
+pub(crate) fn distance<T: Coordinate, P: Euclidean<T>>(from: &P, to: &P) -> T {
+    let a = from.position();
+    let b = to.position();
+
+    let dx = a[0] - b[0];
+    let dy = a[1] - b[1];
+    let dz = a[2] - b[2];
+
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
+
+pub(crate) fn distance_to_segment<T: Coordinate, P: Euclidean<T>>(
+    point: &P,
+    s: (&P, &P),
+    tolerance: T,
+) -> DistanceToSegmentResult<P, T> {
+    let segment_distance = distance(s.0, s.1);
+    if segment_distance <= tolerance {
+        return DistanceToSegmentResult{
+            distance: distance(point, s.0),
+            cut_point: *s.0,
+            cut_ratio: CutRatioResult::Begin,
+        };
+    }
+
+    let a = s.0.position();
+    let b = s.1.position();
+    let p = point.position();
+
+    let (vx, vy, vz) = (b[0] - a[0], b[1] - a[1], b[2] - a[2]);
+    let (ux, uy, uz) = (p[0] - a[0], p[1] - a[1], p[2] - a[2]);
+
+    let ratio = (ux*vx+uy*vy+uz*vz)/(vx*vx+vy*vy+vz*vz);
+    let cut_ratio = ratio.max(T::zero()).min(T::one());
+
+    if cut_ratio <= T::zero() {
+        DistanceToSegmentResult{
+            distance: distance(point, s.0),
+            cut_point: *s.0,
+            cut_ratio: CutRatioResult::Begin,
+        }
+    } else if cut_ratio >= T::one() {
+        DistanceToSegmentResult{
+            distance: distance(point, s.1),
+            cut_point: *s.1,
+            cut_ratio: CutRatioResult::End,
+        }
+    } else {
+        let cut_point = s.0.interpolate(s.1, cut_ratio);
+
+        DistanceToSegmentResult{
+            distance: distance(point, &cut_point),
+            cut_point,
+            cut_ratio: CutRatioResult::Medium(cut_ratio.into()),
         }
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for Point<T> {
+    fn position(&self) -> [T; 3] {
+        [self.0, self.1, T::zero()]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.0.is_finite() && self.1.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Point(lerp(self.0, to.0, ratio), lerp(self.1, to.1, ratio))
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for PointZ<T> {
+    fn position(&self) -> [T; 3] {
+        [self.0, self.1, T::zero()]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.0.is_finite() && self.1.is_finite() && self.2.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        PointZ(lerp(self.0, to.0, ratio), lerp(self.1, to.1, ratio), lerp(self.2, to.2, ratio))
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for PointM<T> {
+    fn position(&self) -> [T; 3] {
+        [self.0, self.1, T::zero()]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.0.is_finite() && self.1.is_finite() && self.2.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        PointM(lerp(self.0, to.0, ratio), lerp(self.1, to.1, ratio), lerp(self.2, to.2, ratio))
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for PointZM<T> {
+    fn position(&self) -> [T; 3] {
+        [self.0, self.1, T::zero()]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.0.is_finite() && self.1.is_finite() && self.2.is_finite() && self.3.is_finite()
+    }
 
-        let vx = s.1.0 - s.0.0;
-        let vy = s.1.1 - s.0.1;
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        PointZM(
+            lerp(self.0, to.0, ratio),
+            lerp(self.1, to.1, ratio),
+            lerp(self.2, to.2, ratio),
+            lerp(self.3, to.3, ratio),
+        )
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for Point3D<T> {
+    fn position(&self) -> [T; 3] {
+        [self.0, self.1, self.2]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.0.is_finite() && self.1.is_finite() && self.2.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Point3D(lerp(self.0, to.0, ratio), lerp(self.1, to.1, ratio), lerp(self.2, to.2, ratio))
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for Point3DM<T> {
+    fn position(&self) -> [T; 3] {
+        [self.0, self.1, self.2]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.0.is_finite() && self.1.is_finite() && self.2.is_finite() && self.3.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Point3DM(
+            lerp(self.0, to.0, ratio),
+            lerp(self.1, to.1, ratio),
+            lerp(self.2, to.2, ratio),
+            lerp(self.3, to.3, ratio),
+        )
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for [T; 2] {
+    fn position(&self) -> [T; 3] {
+        [self[0], self[1], T::zero()]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.iter().all(|v| v.is_finite())
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        [lerp(self[0], to[0], ratio), lerp(self[1], to[1], ratio)]
+    }
+}
 
-        let ux = self.0 - s.0.0;
-        let uy = self.1 - s.0.1;
+impl<T: Coordinate> Euclidean<T> for (T, T) {
+    fn position(&self) -> [T; 3] {
+        [self.0, self.1, T::zero()]
+    }
 
-        let ratio = (ux*vx+uy*vy)/(vx*vx+vy*vy);
-        let cut_ratio = ratio.min(1.0).max(0.0);
+    fn is_finite(&self) -> bool {
+        self.0.is_finite() && self.1.is_finite()
+    }
 
-        if cut_ratio <= 0.0 {
-            let distance = self.distance_to(s.0);
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        (lerp(self.0, to.0, ratio), lerp(self.1, to.1, ratio))
+    }
+}
 
-            DistanceToSegmentResult{
-                distance,
-                cut_point: *s.0,
-                cut_ratio: CutRatioResult::Begin,
+impl<T: Coordinate> Euclidean<T> for [T; 3] {
+    fn position(&self) -> [T; 3] {
+        *self
+    }
+
+    fn is_finite(&self) -> bool {
+        self.iter().all(|v| v.is_finite())
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        [lerp(self[0], to[0], ratio), lerp(self[1], to[1], ratio), lerp(self[2], to[2], ratio)]
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for (T, T, T) {
+    fn position(&self) -> [T; 3] {
+        [self.0, self.1, self.2]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.0.is_finite() && self.1.is_finite() && self.2.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        (lerp(self.0, to.0, ratio), lerp(self.1, to.1, ratio), lerp(self.2, to.2, ratio))
+    }
+}
+
+/// Implements [PolySplit] and [Interpolate] for euclidean point types,
+/// types of other crates list their generics and coordinate type, e.g. `impl [] f32 => Vec2`.
+macro_rules! impl_euclidean_polysplit {
+    (@impl [$($generics:tt)*] $coordinate:ty => $point:ty) => {
+        impl<$($generics)*> $crate::polysplit::PolySplit<$coordinate> for $point {
+            fn distance_to_point(&self, point: &Self) -> $coordinate {
+                $crate::euclidean::distance(self, point)
+            }
+
+            fn distance_to_segment(
+                &self,
+                segment: (&Self, &Self),
+            ) -> $crate::polysplit::DistanceToSegmentResult<Self, $coordinate> {
+                let tolerance = <$coordinate as $crate::euclidean::Coordinate>::DEGENERATE_TOLERANCE;
+                $crate::euclidean::distance_to_segment(self, segment, tolerance)
+            }
+
+            fn distance_to_segment_with_tolerance(
+                &self,
+                segment: (&Self, &Self),
+                tolerance: $coordinate,
+            ) -> $crate::polysplit::DistanceToSegmentResult<Self, $coordinate> {
+                $crate::euclidean::distance_to_segment(self, segment, tolerance)
+            }
+
+            fn is_valid(&self) -> bool {
+                $crate::euclidean::Euclidean::is_finite(self)
+            }
+        }
+
+        impl<$($generics)*> $crate::polysplit::Interpolate<$coordinate> for $point {
+            fn interpolate(segment: (&Self, &Self), ratio: f64) -> Self {
+                let ratio = <$coordinate as $crate::euclidean::Coordinate>::from_f64(ratio);
+                $crate::euclidean::Euclidean::interpolate(segment.0, segment.1, ratio)
             }
-        } else if cut_ratio >= 1.0 {
-            let distance = self.distance_to(s.1);
 
-            DistanceToSegmentResult{
-                distance,
-                cut_point: *s.1,
-                cut_ratio: CutRatioResult::End,
+            fn interpolate_at_distance(segment: (&Self, &Self), distance: $coordinate) -> Self {
+                let tolerance = <$coordinate as $crate::euclidean::Coordinate>::DEGENERATE_TOLERANCE;
+                Self::interpolate_at_distance_with_tolerance(segment, distance, tolerance)
             }
-        } else {
-            let x = s.0.0 + cut_ratio * vx;
-            let y = s.0.1 + cut_ratio * vy;
-            let cut_point = Point(x, y);
-            let distance = self.distance_to(&cut_point);
-
-            DistanceToSegmentResult{
-                distance,
-                cut_point,
-                cut_ratio: CutRatioResult::Medium(cut_ratio),
+
+            fn interpolate_at_distance_with_tolerance(
+                segment: (&Self, &Self),
+                distance: $coordinate,
+                tolerance: $coordinate,
+            ) -> Self {
+                let length = <Self as $crate::polysplit::Interpolate<$coordinate>>::segment_length(segment);
+                if length <= tolerance {
+                    return *segment.0;
+                }
+
+                $crate::euclidean::Euclidean::interpolate(segment.0, segment.1, distance / length)
             }
         }
+    };
+    (impl $generics:tt $coordinate:ty => $($point:ty),+) => {
+        $(
+            impl_euclidean_polysplit!(@impl $generics $coordinate => $point);
+        )+
+    };
+    ($($point:ty),+) => {
+        impl_euclidean_polysplit!(impl [T: Coordinate] T => $($point),+);
+    };
+}
+
+#[cfg(any(feature = "geo-types", feature = "glam", feature = "mint", feature = "nalgebra"))]
+pub(crate) use impl_euclidean_polysplit;
+
+impl_euclidean_polysplit!(Point<T>, PointZ<T>, PointM<T>, PointZM<T>, Point3D<T>, Point3DM<T>);
+
+// Plain arrays and tuples are planar `(x, y)` or spatial `(x, y, z)` points
+impl_euclidean_polysplit!([T; 2], (T, T), [T; 3], (T, T, T));
+
+/// Implements inherent `distance_to` for euclidean point types.
+macro_rules! impl_euclidean_distance_to {
+    ($($point:ident),+) => {
+        $(
+            impl<T: Coordinate> $point<T> {
+                pub fn distance_to(&self, to: &$point<T>) -> T {
+                    distance(self, to)
+                }
+            }
+        )+
+    };
+}
+
+impl_euclidean_distance_to!(Point, PointZ, PointM, PointZM, Point3D, Point3DM);
+
+impl<T> From<[T; 2]> for Point<T> {
+    fn from(p: [T; 2]) -> Self {
+        let [x, y] = p;
+        Point(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for [T; 2] {
+    fn from(p: Point<T>) -> Self {
+        [p.0, p.1]
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from(p: (T, T)) -> Self {
+        Point(p.0, p.1)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.0, p.1)
     }
-}
\ No newline at end of file
+}
+
+impl<T> From<[T; 3]> for Point3D<T> {
+    fn from(p: [T; 3]) -> Self {
+        let [x, y, z] = p;
+        Point3D(x, y, z)
+    }
+}
+
+impl<T> From<Point3D<T>> for [T; 3] {
+    fn from(p: Point3D<T>) -> Self {
+        [p.0, p.1, p.2]
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3D<T> {
+    fn from(p: (T, T, T)) -> Self {
+        Point3D(p.0, p.1, p.2)
+    }
+}
+
+impl<T> From<Point3D<T>> for (T, T, T) {
+    fn from(p: Point3D<T>) -> Self {
+        (p.0, p.1, p.2)
+    }
+}
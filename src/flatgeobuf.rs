@@ -0,0 +1,548 @@
+//! [FlatGeobuf](https://flatgeobuf.org) streaming input and output enabled by `flatgeobuf` feature.
+//!
+//! Polylines are read feature by feature from `LineString` files, spatial index is skipped
+//! and features of other geometry types are rejected.
+//! Segments are written feature by feature as `LineString` file without spatial index
+//! having `SOURCE_ID` and `SEGMENT` columns. `Z` and `M` ordinates are kept if the point type has them.
+use alloc::collections::BTreeMap;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use std::io::{self, Read, Write};
+
+use flatbuffers::{FlatBufferBuilder, Follow, ForwardsUOffset, InvalidFlatbuffer, Table, Verifiable, Verifier, Vector, VOffsetT, WIPOffset};
+
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, with_polyline_id, PolySplit, PolySplitError, Result};
+
+const MAGIC: [u8; 8] = [b'f', b'g', b'b', 3, b'f', b'g', b'b', 0];
+
+const GEOMETRY_TYPE_UNKNOWN: u8 = 0;
+const GEOMETRY_TYPE_LINE_STRING: u8 = 2;
+
+const COLUMN_TYPE_UINT: u8 = 6;
+const COLUMN_TYPE_STRING: u8 = 11;
+
+/// Size of packed R-tree node item: bounding box and offset.
+const NODE_ITEM_LEN: u64 = 40;
+
+/// Reads `LineString` features one by one with their key property.
+pub struct LineStringReader<R> {
+    reader: R,
+    geometry_type: u8,
+    has_z: bool,
+    has_m: bool,
+    columns: Vec<u8>,
+    key_column: usize,
+}
+
+impl<R: Read> LineStringReader<R> {
+    /// Reads header of the file and skips spatial index, `key` is the name of the column
+    /// identifying features.
+    pub fn new(mut reader: R, key: &str) -> Result<Self> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if magic[..7] != MAGIC[..7] {
+            return Err(invalid_format("FlatGeobuf magic bytes are expected"));
+        }
+
+        let buffer = read_size_prefixed(&mut reader)?
+            .ok_or_else(|| invalid_format("FlatGeobuf header is expected"))?;
+        let header = flatbuffers::size_prefixed_root::<Header>(&buffer).map_err(flatbuffer_error)?;
+
+        let mut columns = Vec::new();
+        let mut key_column = None;
+        for column in header.columns().iter().flatten() {
+            if column.name() == Some(key) {
+                key_column = Some(columns.len());
+            }
+            columns.push(column.column_type());
+        }
+        let key_column = key_column.ok_or_else(|| invalid_format(&format!("key column {} is not found", key)))?;
+
+        let features_count = header.features_count();
+        let index_node_size = header.index_node_size();
+        if features_count > 0 && index_node_size > 0 {
+            let index_len = index_len(features_count, index_node_size);
+            if io::copy(&mut (&mut reader).take(index_len), &mut io::sink())? != index_len {
+                return Err(invalid_format("unexpected end of spatial index"));
+            }
+        }
+
+        Ok(LineStringReader {
+            reader,
+            // Geometry type of the header is the default of features, they are checked one by one
+            geometry_type: header.geometry_type(),
+            has_z: header.has_z(),
+            has_m: header.has_m(),
+            columns,
+            key_column,
+        })
+    }
+
+    /// Reads the next feature, `None` if there are no more features.
+    pub fn read_line_string<P: Ordinates<f64>>(&mut self) -> Result<Option<(String, Vec<P>)>> {
+        let buffer = match read_size_prefixed(&mut self.reader)? {
+            Some(buffer) => buffer,
+            None => return Ok(None),
+        };
+
+        let feature = flatbuffers::size_prefixed_root::<Feature>(&buffer).map_err(flatbuffer_error)?;
+        let key = self
+            .key(feature.properties().map(|properties| properties.bytes()).unwrap_or_default())?
+            .ok_or_else(|| invalid_format("key property is expected"))?;
+
+        let geometry = feature
+            .geometry()
+            .ok_or_else(|| invalid_format("feature geometry is expected"))?;
+        let geometry_type = geometry.geometry_type().unwrap_or(self.geometry_type);
+        if geometry_type != GEOMETRY_TYPE_LINE_STRING {
+            let error = invalid_format(&format!("LineString geometry is expected, got {}", geometry_type));
+            return Err(with_polyline_id(error, &key));
+        }
+
+        let vector = |values: Option<flatbuffers::Vector<'_, f64>>, is_defined: bool| -> Vec<f64> {
+            values.filter(|_| is_defined).map(|values| values.iter().collect()).unwrap_or_default()
+        };
+        let xy = vector(geometry.xy(), true);
+        let z = vector(geometry.z(), self.has_z);
+        let m = vector(geometry.m(), self.has_m);
+
+        let points = xy
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(i, xy)| P::from_ordinates(xy[0], xy[1], z.get(i).copied(), m.get(i).copied()))
+            .collect();
+
+        Ok(Some((key, points)))
+    }
+
+    /// Finds key value in feature properties encoded as column index followed by the value.
+    fn key(&self, mut properties: &[u8]) -> Result<Option<String>> {
+        let error = || invalid_format("invalid feature properties");
+
+        while properties.len() >= 2 {
+            let column = u16::from_le_bytes([properties[0], properties[1]]) as usize;
+            let column_type = *self.columns.get(column).ok_or_else(error)?;
+            properties = &properties[2..];
+
+            let len = match column_type {
+                0..=2 => 1,
+                3 | 4 => 2,
+                5 | 6 | 9 => 4,
+                7 | 8 | 10 => 8,
+                _ => {
+                    let len = properties.get(..4).ok_or_else(error)?;
+                    properties = &properties[4..];
+                    u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize
+                }
+            };
+
+            let value = properties.get(..len).ok_or_else(error)?;
+            properties = &properties[len..];
+
+            if column == self.key_column {
+                return Ok(Some(property_to_string(column_type, value)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Writes segments as `LineString` features one by one.
+pub struct SegmentWriter<W, P> {
+    writer: W,
+    builder: FlatBufferBuilder<'static>,
+    point: PhantomData<P>,
+}
+
+impl<W: Write, P: Ordinates<f64>> SegmentWriter<W, P> {
+    /// Writes header of the file, the number of features is unknown and spatial index is not written.
+    pub fn new(mut writer: W) -> Result<Self> {
+        let mut builder = FlatBufferBuilder::new();
+
+        let columns = [("SOURCE_ID", COLUMN_TYPE_STRING), ("SEGMENT", COLUMN_TYPE_UINT)].map(|(name, column_type)| {
+            let name = builder.create_string(name);
+            let column = builder.start_table();
+            builder.push_slot_always(Column::VT_NAME, name);
+            builder.push_slot(Column::VT_TYPE, column_type, 0);
+            builder.end_table(column)
+        });
+        let columns = builder.create_vector(&columns);
+
+        let header = builder.start_table();
+        builder.push_slot(Header::VT_GEOMETRY_TYPE, GEOMETRY_TYPE_LINE_STRING, GEOMETRY_TYPE_UNKNOWN);
+        builder.push_slot(Header::VT_HAS_Z, P::HAS_Z, false);
+        builder.push_slot(Header::VT_HAS_M, P::HAS_M, false);
+        builder.push_slot_always(Header::VT_COLUMNS, columns);
+        builder.push_slot_always(Header::VT_INDEX_NODE_SIZE, 0u16);
+        let header = builder.end_table(header);
+        builder.finish_size_prefixed(header, None);
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(builder.finished_data())?;
+        builder.reset();
+
+        Ok(SegmentWriter { writer, builder, point: PhantomData })
+    }
+
+    /// Writes segments of the polyline with `id` as features.
+    pub fn write_segments(&mut self, id: &str, segments: &[Vec<P>]) -> Result<()> {
+        for (segment_index, segment) in segments.iter().enumerate() {
+            let builder = &mut self.builder;
+
+            let mut properties = Vec::new();
+            properties.extend_from_slice(&0u16.to_le_bytes());
+            properties.extend_from_slice(&(id.len() as u32).to_le_bytes());
+            properties.extend_from_slice(id.as_bytes());
+            properties.extend_from_slice(&1u16.to_le_bytes());
+            properties.extend_from_slice(&(segment_index as u32).to_le_bytes());
+            let properties = builder.create_vector(&properties);
+
+            let xy: Vec<f64> = segment.iter().flat_map(|p| [p.x(), p.y()]).collect();
+            let xy = builder.create_vector(&xy);
+            let z = ordinates(builder, segment.iter().filter_map(|p| p.z()));
+            let m = ordinates(builder, segment.iter().filter_map(|p| p.m()));
+
+            let geometry = builder.start_table();
+            builder.push_slot_always(Geometry::VT_XY, xy);
+            if P::HAS_Z {
+                builder.push_slot_always(Geometry::VT_Z, z);
+            }
+            if P::HAS_M {
+                builder.push_slot_always(Geometry::VT_M, m);
+            }
+            let geometry = builder.end_table(geometry);
+
+            let feature = builder.start_table();
+            builder.push_slot_always(Feature::VT_GEOMETRY, geometry);
+            builder.push_slot_always(Feature::VT_PROPERTIES, properties);
+            let feature = builder.end_table(feature);
+            builder.finish_size_prefixed(feature, None);
+
+            self.writer.write_all(builder.finished_data())?;
+            builder.reset();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Streams polylines feature by feature, splits each by the points of its key
+/// and streams the segments.
+///
+/// Splitting stops at the first feature that cannot be read or split, errors of the feature
+/// are prefixed with its polyline id. Segments of the previous features are already written at that point,
+/// so the output looks like a complete file missing the rest of features and should be
+/// discarded.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use polysplit::euclidean::Point;
+/// use polysplit::flatgeobuf::{flatgeobuf_split, LineStringReader, SegmentWriter};
+///
+/// let mut polylines = SegmentWriter::new(Vec::new()).unwrap();
+/// polylines.write_segments("road", &[vec![Point(0.0, 0.0), Point(10.0, 0.0)]]).unwrap();
+///
+/// let points = BTreeMap::from([("road".to_string(), vec![Point(1.0, 1.0), Point(9.0, 1.0)])]);
+///
+/// let mut segments = Vec::new();
+/// flatgeobuf_split(polylines.into_inner().as_slice(), &points, &mut segments, "SOURCE_ID", None).unwrap();
+///
+/// let mut reader = LineStringReader::new(segments.as_slice(), "SOURCE_ID").unwrap();
+/// let (id, segment) = reader.read_line_string::<Point>().unwrap().unwrap();
+///
+/// assert_eq!(id, "road");
+/// assert_eq!(segment.len(), 2);
+/// ```
+pub fn flatgeobuf_split<P>(
+    reader: impl Read,
+    points: &BTreeMap<String, Vec<P>>,
+    writer: impl Write,
+    key: &str,
+    distance_threshold: Option<f64>,
+) -> Result<()>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let mut reader = LineStringReader::new(reader, key)?;
+    let mut writer = SegmentWriter::new(writer)?;
+
+    while let Some((id, polyline)) = reader.read_line_string::<P>()? {
+        let points = points.get(&id).map(Vec::as_slice).unwrap_or_default();
+
+        let segments = polyline_split(&polyline, points, distance_threshold)
+            .map_err(|error| with_polyline_id(error, &id))?;
+
+        writer.write_segments(&id, &segments)?;
+    }
+
+    writer.into_inner().flush()?;
+    Ok(())
+}
+
+fn ordinates<'a>(builder: &mut FlatBufferBuilder<'a>, values: impl Iterator<Item = f64>) -> WIPOffset<flatbuffers::Vector<'a, f64>> {
+    let values: Vec<f64> = values.collect();
+    builder.create_vector(&values)
+}
+
+/// Reads size prefixed buffer keeping the prefix, so alignment of the fields is verified
+/// as it was written, `None` at the end of the input.
+fn read_size_prefixed(reader: &mut impl Read) -> Result<Option<Vec<u8>>> {
+    let mut len = [0; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(error.into()),
+    }
+
+    // Length is not trusted, so the buffer grows by the bytes actually read
+    let mut buffer = len.to_vec();
+    let len = u64::from(u32::from_le_bytes(len));
+    if reader.take(len).read_to_end(&mut buffer)? as u64 != len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(Some(buffer))
+}
+
+/// Size of packed Hilbert R-tree.
+fn index_len(features_count: u64, node_size: u16) -> u64 {
+    let node_size = u64::from(node_size.max(2));
+
+    let mut level_len = features_count;
+    let mut len = level_len;
+    loop {
+        level_len = level_len.div_ceil(node_size);
+        len += level_len;
+        if level_len == 1 {
+            break;
+        }
+    }
+
+    len * NODE_ITEM_LEN
+}
+
+fn property_to_string(column_type: u8, value: &[u8]) -> String {
+    let bytes = || {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&value[..8]);
+        bytes
+    };
+
+    match column_type {
+        0 => (value[0] as i8).to_string(),
+        1 => value[0].to_string(),
+        2 => (value[0] != 0).to_string(),
+        3 => i16::from_le_bytes([value[0], value[1]]).to_string(),
+        4 => u16::from_le_bytes([value[0], value[1]]).to_string(),
+        5 => i32::from_le_bytes([value[0], value[1], value[2], value[3]]).to_string(),
+        6 => u32::from_le_bytes([value[0], value[1], value[2], value[3]]).to_string(),
+        7 => i64::from_le_bytes(bytes()).to_string(),
+        8 => u64::from_le_bytes(bytes()).to_string(),
+        9 => f32::from_le_bytes([value[0], value[1], value[2], value[3]]).to_string(),
+        10 => f64::from_le_bytes(bytes()).to_string(),
+        _ => String::from_utf8_lossy(value).into_owned(),
+    }
+}
+
+fn flatbuffer_error(error: InvalidFlatbuffer) -> PolySplitError {
+    invalid_format(&format!("invalid FlatBuffers table: {}", error))
+}
+
+// Tables of FlatGeobuf schema declared the way `flatc` generates them: fields are addressed
+// by vtable offsets of the schema and verified before access, only used fields are declared.
+
+struct Header<'a> {
+    table: Table<'a>,
+}
+
+impl<'a> Follow<'a> for Header<'a> {
+    type Inner = Header<'a>;
+
+    unsafe fn follow(buffer: &'a [u8], location: usize) -> Self::Inner {
+        // Safety: the location is checked by the verifier
+        Header { table: unsafe { Table::new(buffer, location) } }
+    }
+}
+
+impl<'a> Header<'a> {
+    const VT_GEOMETRY_TYPE: VOffsetT = 8;
+    const VT_HAS_Z: VOffsetT = 10;
+    const VT_HAS_M: VOffsetT = 12;
+    const VT_COLUMNS: VOffsetT = 18;
+    const VT_FEATURES_COUNT: VOffsetT = 20;
+    const VT_INDEX_NODE_SIZE: VOffsetT = 22;
+
+    // Safety of the accessors: fields are verified to have their types
+
+    fn geometry_type(&self) -> u8 {
+        unsafe { self.table.get::<u8>(Self::VT_GEOMETRY_TYPE, Some(GEOMETRY_TYPE_UNKNOWN)).unwrap_or_default() }
+    }
+
+    fn has_z(&self) -> bool {
+        unsafe { self.table.get::<bool>(Self::VT_HAS_Z, Some(false)).unwrap_or_default() }
+    }
+
+    fn has_m(&self) -> bool {
+        unsafe { self.table.get::<bool>(Self::VT_HAS_M, Some(false)).unwrap_or_default() }
+    }
+
+    fn columns(&self) -> Option<Vector<'a, ForwardsUOffset<Column<'a>>>> {
+        unsafe { self.table.get::<ForwardsUOffset<Vector<'a, ForwardsUOffset<Column>>>>(Self::VT_COLUMNS, None) }
+    }
+
+    fn features_count(&self) -> u64 {
+        unsafe { self.table.get::<u64>(Self::VT_FEATURES_COUNT, Some(0)).unwrap_or_default() }
+    }
+
+    fn index_node_size(&self) -> u16 {
+        unsafe { self.table.get::<u16>(Self::VT_INDEX_NODE_SIZE, Some(16)).unwrap_or_default() }
+    }
+}
+
+impl Verifiable for Header<'_> {
+    fn run_verifier(verifier: &mut Verifier, position: usize) -> core::result::Result<(), InvalidFlatbuffer> {
+        verifier
+            .visit_table(position)?
+            .visit_field::<u8>("geometry_type", Self::VT_GEOMETRY_TYPE, false)?
+            .visit_field::<bool>("has_z", Self::VT_HAS_Z, false)?
+            .visit_field::<bool>("has_m", Self::VT_HAS_M, false)?
+            .visit_field::<ForwardsUOffset<Vector<'_, ForwardsUOffset<Column>>>>("columns", Self::VT_COLUMNS, false)?
+            .visit_field::<u64>("features_count", Self::VT_FEATURES_COUNT, false)?
+            .visit_field::<u16>("index_node_size", Self::VT_INDEX_NODE_SIZE, false)?
+            .finish();
+        Ok(())
+    }
+}
+
+struct Column<'a> {
+    table: Table<'a>,
+}
+
+impl<'a> Follow<'a> for Column<'a> {
+    type Inner = Column<'a>;
+
+    unsafe fn follow(buffer: &'a [u8], location: usize) -> Self::Inner {
+        // Safety: the location is checked by the verifier
+        Column { table: unsafe { Table::new(buffer, location) } }
+    }
+}
+
+impl<'a> Column<'a> {
+    const VT_NAME: VOffsetT = 4;
+    const VT_TYPE: VOffsetT = 6;
+
+    fn name(&self) -> Option<&'a str> {
+        unsafe { self.table.get::<ForwardsUOffset<&str>>(Self::VT_NAME, None) }
+    }
+
+    fn column_type(&self) -> u8 {
+        unsafe { self.table.get::<u8>(Self::VT_TYPE, Some(0)).unwrap_or_default() }
+    }
+}
+
+impl Verifiable for Column<'_> {
+    fn run_verifier(verifier: &mut Verifier, position: usize) -> core::result::Result<(), InvalidFlatbuffer> {
+        verifier
+            .visit_table(position)?
+            .visit_field::<ForwardsUOffset<&str>>("name", Self::VT_NAME, true)?
+            .visit_field::<u8>("type", Self::VT_TYPE, false)?
+            .finish();
+        Ok(())
+    }
+}
+
+struct Feature<'a> {
+    table: Table<'a>,
+}
+
+impl<'a> Follow<'a> for Feature<'a> {
+    type Inner = Feature<'a>;
+
+    unsafe fn follow(buffer: &'a [u8], location: usize) -> Self::Inner {
+        // Safety: the location is checked by the verifier
+        Feature { table: unsafe { Table::new(buffer, location) } }
+    }
+}
+
+impl<'a> Feature<'a> {
+    const VT_GEOMETRY: VOffsetT = 4;
+    const VT_PROPERTIES: VOffsetT = 6;
+
+    fn geometry(&self) -> Option<Geometry<'a>> {
+        unsafe { self.table.get::<ForwardsUOffset<Geometry>>(Self::VT_GEOMETRY, None) }
+    }
+
+    fn properties(&self) -> Option<Vector<'a, u8>> {
+        unsafe { self.table.get::<ForwardsUOffset<Vector<'a, u8>>>(Self::VT_PROPERTIES, None) }
+    }
+}
+
+impl Verifiable for Feature<'_> {
+    fn run_verifier(verifier: &mut Verifier, position: usize) -> core::result::Result<(), InvalidFlatbuffer> {
+        verifier
+            .visit_table(position)?
+            .visit_field::<ForwardsUOffset<Geometry>>("geometry", Self::VT_GEOMETRY, false)?
+            .visit_field::<ForwardsUOffset<Vector<'_, u8>>>("properties", Self::VT_PROPERTIES, false)?
+            .finish();
+        Ok(())
+    }
+}
+
+struct Geometry<'a> {
+    table: Table<'a>,
+}
+
+impl<'a> Follow<'a> for Geometry<'a> {
+    type Inner = Geometry<'a>;
+
+    unsafe fn follow(buffer: &'a [u8], location: usize) -> Self::Inner {
+        // Safety: the location is checked by the verifier
+        Geometry { table: unsafe { Table::new(buffer, location) } }
+    }
+}
+
+impl<'a> Geometry<'a> {
+    const VT_XY: VOffsetT = 6;
+    const VT_Z: VOffsetT = 8;
+    const VT_M: VOffsetT = 10;
+    const VT_TYPE: VOffsetT = 16;
+
+    fn xy(&self) -> Option<Vector<'a, f64>> {
+        unsafe { self.table.get::<ForwardsUOffset<Vector<'a, f64>>>(Self::VT_XY, None) }
+    }
+
+    fn z(&self) -> Option<Vector<'a, f64>> {
+        unsafe { self.table.get::<ForwardsUOffset<Vector<'a, f64>>>(Self::VT_Z, None) }
+    }
+
+    fn m(&self) -> Option<Vector<'a, f64>> {
+        unsafe { self.table.get::<ForwardsUOffset<Vector<'a, f64>>>(Self::VT_M, None) }
+    }
+
+    /// Geometry type, `None` if it is not defined and the type of the header is used.
+    fn geometry_type(&self) -> Option<u8> {
+        unsafe { self.table.get::<u8>(Self::VT_TYPE, None) }
+    }
+}
+
+impl Verifiable for Geometry<'_> {
+    fn run_verifier(verifier: &mut Verifier, position: usize) -> core::result::Result<(), InvalidFlatbuffer> {
+        verifier
+            .visit_table(position)?
+            .visit_field::<ForwardsUOffset<Vector<'_, f64>>>("xy", Self::VT_XY, false)?
+            .visit_field::<ForwardsUOffset<Vector<'_, f64>>>("z", Self::VT_Z, false)?
+            .visit_field::<ForwardsUOffset<Vector<'_, f64>>>("m", Self::VT_M, false)?
+            .visit_field::<u8>("type", Self::VT_TYPE, false)?
+            .finish();
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Integration with [geo-types](https://docs.rs/geo-types) enabled by `geo-types` feature.
+//!
+//! [PolySplit](crate::PolySplit) and [Interpolate](crate::Interpolate) are implemented
+//! for `Coord` and `Point` with euclidean semantics, so they can be passed to [polyline_split] directly.
+use alloc::vec::Vec;
+
+use geo_types::{Coord, CoordFloat, LineString, MultiLineString, MultiPoint, Point};
+
+use crate::euclidean::{impl_euclidean_polysplit, lerp, Coordinate, Euclidean};
+use crate::polysplit::{polyline_split, polyline_split_with_tolerance, Result};
+
+impl<T: Coordinate + CoordFloat> Euclidean<T> for Coord<T> {
+    fn position(&self) -> [T; 3] {
+        [self.x, self.y, T::zero()]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Coord { x: lerp(self.x, to.x, ratio), y: lerp(self.y, to.y, ratio) }
+    }
+}
+
+impl<T: Coordinate + CoordFloat> Euclidean<T> for Point<T> {
+    fn position(&self) -> [T; 3] {
+        self.0.position()
+    }
+
+    fn is_finite(&self) -> bool {
+        Euclidean::is_finite(&self.0)
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Point(self.0.interpolate(&to.0, ratio))
+    }
+}
+
+impl_euclidean_polysplit!(impl [T: Coordinate + CoordFloat] T => Coord<T>, Point<T>);
+
+/// Splits line string into line strings by the defined points.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types::{line_string, MultiPoint, Point};
+/// use polysplit::geo_types::line_string_split;
+///
+/// let line_string = line_string![(x: 0.0, y: 0.0), (x: 10.0, y: 0.0), (x: 20.0, y: 0.0)];
+/// let points = MultiPoint(vec![Point::new(1.0, 1.0), Point::new(19.0, 1.0)]);
+///
+/// let segments = line_string_split(&line_string, &points, None).unwrap();
+///
+/// assert_eq!(segments.0.len(), 1);
+/// ```
+pub fn line_string_split<T>(
+    line_string: &LineString<T>,
+    points: &MultiPoint<T>,
+    distance_threshold: Option<T>,
+) -> Result<MultiLineString<T>>
+where
+    T: Coordinate + CoordFloat,
+{
+    let points: Vec<Coord<T>> = points.iter().map(|p| p.0).collect();
+    let segments = polyline_split(&line_string.0, &points, distance_threshold)?;
+
+    Ok(segments.into_iter().map(LineString).collect())
+}
+
+/// Splits line string into line strings by the defined points using geometric tolerance,
+/// see [polyline_split_with_tolerance].
+pub fn line_string_split_with_tolerance<T>(
+    line_string: &LineString<T>,
+    points: &MultiPoint<T>,
+    distance_threshold: Option<T>,
+    tolerance: T,
+) -> Result<MultiLineString<T>>
+where
+    T: Coordinate + CoordFloat,
+{
+    let points: Vec<Coord<T>> = points.iter().map(|p| p.0).collect();
+    let segments = polyline_split_with_tolerance(&line_string.0, &points, distance_threshold, tolerance)?;
+
+    Ok(segments.into_iter().map(LineString).collect())
+}
@@ -0,0 +1,323 @@
+//! [GeoArrow](https://geoarrow.org) arrays processing enabled by `geoarrow` feature.
+//!
+//! Polylines are read from `LineString` arrays and points from `MultiPoint` arrays
+//! with separated (`Struct<x, y, z, m>`) or interleaved (`FixedSizeList<Float64>`) coordinates,
+//! they are split row by row into `MultiLineString` array with separated coordinates.
+//! `Z` and `M` ordinates are kept if the point type has them, null rows stay null.
+use alloc::sync::Arc;
+use core::fmt::Debug;
+use std::collections::HashMap;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::Float64Type;
+use arrow_array::{Array, ArrayRef, FixedSizeListArray, Float64Array, ListArray, RecordBatch, StructArray};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_schema::{DataType, Field, FieldRef, Fields, Schema};
+
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, PolySplit, PolySplitError, Result};
+
+const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+
+/// Reads polylines from `geoarrow.linestring` array.
+pub fn read_line_strings<P: Ordinates<f64>>(array: &dyn Array) -> Result<Vec<Option<Vec<P>>>> {
+    read_lists(array)
+}
+
+/// Reads points from `geoarrow.multipoint` array.
+pub fn read_multi_points<P: Ordinates<f64>>(array: &dyn Array) -> Result<Vec<Option<Vec<P>>>> {
+    read_lists(array)
+}
+
+/// Reads segments from `geoarrow.multilinestring` array.
+pub fn read_multi_line_strings<P: Ordinates<f64>>(array: &dyn Array) -> Result<Vec<Option<Vec<Vec<P>>>>> {
+    let list = array
+        .as_list_opt::<i32>()
+        .ok_or_else(|| invalid_format("MultiLineString list array is expected"))?;
+    let mut line_strings: Vec<Option<Vec<P>>> = read_lists(list.values().as_ref())?;
+    let offsets = list.value_offsets();
+
+    Ok((0..list.len())
+        .map(|i| {
+            let segments = (offsets[i] as usize..offsets[i + 1] as usize)
+                .map(|index| line_strings[index].take().unwrap_or_default())
+                .collect();
+            list.is_valid(i).then_some(segments)
+        })
+        .collect())
+}
+
+/// Writes segments as `geoarrow.multilinestring` array with separated coordinates.
+pub fn write_multi_line_strings<P: Ordinates<f64>>(rows: &[Option<Vec<Vec<P>>>]) -> ListArray {
+    let points = rows.iter().flatten().flatten().flatten();
+
+    let mut coordinates: Vec<ArrayRef> = vec![
+        Arc::new(points.clone().map(|p| p.x()).collect::<Float64Array>()),
+        Arc::new(points.clone().map(|p| p.y()).collect::<Float64Array>()),
+    ];
+    if P::HAS_Z {
+        coordinates.push(Arc::new(points.clone().filter_map(|p| p.z()).collect::<Float64Array>()));
+    }
+    if P::HAS_M {
+        coordinates.push(Arc::new(points.filter_map(|p| p.m()).collect::<Float64Array>()));
+    }
+
+    let vertexes = StructArray::new(coordinate_fields::<P>(), coordinates, None);
+    let line_strings = ListArray::new(
+        vertex_field::<P>(),
+        OffsetBuffer::from_lengths(rows.iter().flatten().flatten().map(Vec::len)),
+        Arc::new(vertexes),
+        None,
+    );
+
+    ListArray::new(
+        line_string_field::<P>(),
+        OffsetBuffer::from_lengths(rows.iter().map(|row| row.as_ref().map_or(0, Vec::len))),
+        Arc::new(line_strings),
+        Some(NullBuffer::from_iter(rows.iter().map(Option::is_some))),
+    )
+}
+
+/// Returns nullable `geoarrow.multilinestring` field of the array written by [write_multi_line_strings].
+pub fn multi_line_string_field<P: Ordinates<f64>>(name: &str) -> Field {
+    Field::new(name, DataType::List(line_string_field::<P>()), true).with_metadata(HashMap::from([(
+        EXTENSION_NAME_KEY.to_string(),
+        "geoarrow.multilinestring".to_string(),
+    )]))
+}
+
+/// Splits polylines by points row by row, the row is null if any of them is null.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use arrow_array::{Array, FixedSizeListArray, Float64Array, ListArray};
+/// use arrow_buffer::OffsetBuffer;
+/// use arrow_schema::{DataType, Field};
+/// use polysplit::euclidean::Point;
+/// use polysplit::geoarrow::{geoarrow_split, read_multi_line_strings};
+///
+/// let interleaved = |values: Vec<f64>| {
+///     let field = Arc::new(Field::new("xy", DataType::Float64, false));
+///     let coordinates = FixedSizeListArray::new(field.clone(), 2, Arc::new(Float64Array::from(values)), None);
+///     let field = Arc::new(Field::new("vertices", coordinates.data_type().clone(), false));
+///     ListArray::new(field, OffsetBuffer::from_lengths([2]), Arc::new(coordinates), None)
+/// };
+///
+/// let line_strings = interleaved(vec![0.0, 0.0, 10.0, 0.0]);
+/// let points = interleaved(vec![1.0, 1.0, 9.0, 1.0]);
+///
+/// let segments = geoarrow_split::<Point>(&line_strings, &points, None).unwrap();
+/// let segments = read_multi_line_strings::<Point>(&segments).unwrap();
+///
+/// assert_eq!(segments[0].as_ref().unwrap()[0].len(), 2);
+/// ```
+pub fn geoarrow_split<P>(line_strings: &dyn Array, points: &dyn Array, distance_threshold: Option<f64>) -> Result<ListArray>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    if line_strings.len() != points.len() {
+        return Err(invalid_format("line strings and points arrays have different lengths"));
+    }
+
+    let line_strings: Vec<Option<Vec<P>>> = read_line_strings(line_strings)?;
+    let points: Vec<Option<Vec<P>>> = read_multi_points(points)?;
+
+    let rows = line_strings
+        .into_iter()
+        .zip(points)
+        .enumerate()
+        .map(|(row_index, row)| match row {
+            (Some(polyline), Some(points)) => polyline_split(&polyline, &points, distance_threshold)
+                .map(Some)
+                .map_err(|error| PolySplitError{
+                    kind: error.kind,
+                    message: format!("row {}: {}", row_index, error.message),
+                }),
+            _ => Ok(None),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(write_multi_line_strings(&rows))
+}
+
+/// Splits polylines of the record batch by points row by row, polylines column is replaced
+/// by `MultiLineString` column of the same name and points column is removed.
+pub fn record_batch_split<P>(
+    batch: &RecordBatch,
+    line_string_column: &str,
+    points_column: &str,
+    distance_threshold: Option<f64>,
+) -> Result<RecordBatch>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let schema = batch.schema();
+    let line_string_index = column_index(&schema, line_string_column)?;
+    let points_index = column_index(&schema, points_column)?;
+
+    let segments: ArrayRef = Arc::new(geoarrow_split::<P>(
+        batch.column(line_string_index),
+        batch.column(points_index),
+        distance_threshold,
+    )?);
+
+    let columns = batch
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != points_index)
+        .map(|(index, column)| if index == line_string_index { segments.clone() } else { column.clone() })
+        .collect();
+
+    let schema = split_schema::<P>(&schema, line_string_column, points_column)?;
+    RecordBatch::try_new(Arc::new(schema), columns).map_err(|e| invalid_format(&e.to_string()))
+}
+
+/// Returns schema of the record batches returned by [record_batch_split].
+pub fn split_schema<P: Ordinates<f64>>(schema: &Schema, line_string_column: &str, points_column: &str) -> Result<Schema> {
+    let line_string_index = column_index(schema, line_string_column)?;
+    let points_index = column_index(schema, points_column)?;
+
+    let fields: Vec<Field> = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != points_index)
+        .map(|(index, field)| {
+            if index == line_string_index {
+                multi_line_string_field::<P>(field.name())
+            } else {
+                field.as_ref().clone()
+            }
+        })
+        .collect();
+
+    Ok(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+fn column_index(schema: &Schema, name: &str) -> Result<usize> {
+    schema
+        .index_of(name)
+        .map_err(|_| invalid_format(&format!("column {} is not found", name)))
+}
+
+fn coordinate_fields<P: Ordinates<f64>>() -> Fields {
+    let mut fields = vec![
+        Field::new("x", DataType::Float64, false),
+        Field::new("y", DataType::Float64, false),
+    ];
+    if P::HAS_Z {
+        fields.push(Field::new("z", DataType::Float64, false));
+    }
+    if P::HAS_M {
+        fields.push(Field::new("m", DataType::Float64, false));
+    }
+
+    Fields::from(fields)
+}
+
+fn vertex_field<P: Ordinates<f64>>() -> FieldRef {
+    Arc::new(Field::new("vertices", DataType::Struct(coordinate_fields::<P>()), false))
+}
+
+fn line_string_field<P: Ordinates<f64>>() -> FieldRef {
+    Arc::new(Field::new("linestrings", DataType::List(vertex_field::<P>()), false))
+}
+
+fn read_lists<P: Ordinates<f64>>(array: &dyn Array) -> Result<Vec<Option<Vec<P>>>> {
+    let list = array
+        .as_list_opt::<i32>()
+        .ok_or_else(|| invalid_format("list array of coordinates is expected"))?;
+    let coordinates = Coordinates::new(list.values().as_ref())?;
+    let offsets = list.value_offsets();
+
+    Ok((0..list.len())
+        .map(|i| {
+            list.is_valid(i).then(|| {
+                (offsets[i] as usize..offsets[i + 1] as usize)
+                    .map(|index| coordinates.point(index))
+                    .collect()
+            })
+        })
+        .collect())
+}
+
+/// Coordinate arrays, `z` and `m` are optional.
+enum Coordinates<'a> {
+    Separated {
+        x: &'a Float64Array,
+        y: &'a Float64Array,
+        z: Option<&'a Float64Array>,
+        m: Option<&'a Float64Array>,
+    },
+    Interleaved {
+        list: &'a FixedSizeListArray,
+        values: &'a Float64Array,
+        z: Option<usize>,
+        m: Option<usize>,
+    },
+}
+
+impl<'a> Coordinates<'a> {
+    fn new(array: &'a dyn Array) -> Result<Self> {
+        let float64 = |array: &'a ArrayRef| {
+            array
+                .as_primitive_opt::<Float64Type>()
+                .ok_or_else(|| invalid_format("Float64 coordinates are expected"))
+        };
+
+        if let Some(array) = array.as_struct_opt() {
+            let ordinates = ["x", "y", "z", "m"].map(|name| array.column_by_name(name).map(float64));
+            let [x, y, z, m] = ordinates;
+            let (Some(x), Some(y)) = (x, y) else {
+                return Err(invalid_format("x and y coordinates are expected"));
+            };
+
+            return Ok(Coordinates::Separated { x: x?, y: y?, z: z.transpose()?, m: m.transpose()? });
+        }
+
+        if let Some(array) = array.as_fixed_size_list_opt() {
+            let DataType::FixedSizeList(field, _) = array.data_type() else {
+                return Err(invalid_format("fixed size list is expected"));
+            };
+
+            let size = array.value_length() as usize;
+            let (z, m) = match (size, field.name().as_str()) {
+                (2, _) => (None, None),
+                (3, "xym") => (None, Some(2)),
+                (3, _) => (Some(2), None),
+                (4, _) => (Some(2), Some(3)),
+                _ => return Err(invalid_format(&format!("unexpected number of ordinates {}", size))),
+            };
+
+            let values = float64(array.values())?;
+            return Ok(Coordinates::Interleaved { list: array, values, z, m });
+        }
+
+        Err(invalid_format("struct or fixed size list coordinates are expected"))
+    }
+
+    fn point<P: Ordinates<f64>>(&self, index: usize) -> P {
+        match self {
+            Coordinates::Separated { x, y, z, m } => P::from_ordinates(
+                x.value(index),
+                y.value(index),
+                z.map(|z| z.value(index)),
+                m.map(|m| m.value(index)),
+            ),
+            Coordinates::Interleaved { list, values, z, m } => {
+                // Values of the sliced array start at its offset
+                let position = list.value_offset(index) as usize;
+                P::from_ordinates(
+                    values.value(position),
+                    values.value(position + 1),
+                    z.map(|z| values.value(position + z)),
+                    m.map(|m| values.value(position + m)),
+                )
+            }
+        }
+    }
+}
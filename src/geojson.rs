@@ -0,0 +1,124 @@
+//! [GeoJSON](https://geojson.org) input and output enabled by `geojson` feature.
+//!
+//! Polylines are read from `LineString` and points from `MultiPoint` geometries
+//! (or collections of `Point` features), positions keep elevation if the point type has it.
+//! Segments are written as `LineString` features with `segment_index`,
+//! `from_point_index` and `to_point_index` properties.
+use core::fmt::Debug;
+
+use geojson::{Feature, FeatureCollection, GeoJson, Geometry, JsonObject, Position, Value};
+
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, PolySplit, Result};
+
+/// Reads polyline from `LineString` geometry or feature.
+pub fn read_line_string<P: Ordinates<f64>>(geojson: &GeoJson) -> Result<Vec<P>> {
+    match geometry_value(geojson) {
+        Some(Value::LineString(positions)) => positions.iter().map(read_position).collect(),
+        _ => Err(invalid_format("LineString geometry is expected")),
+    }
+}
+
+/// Reads points from `MultiPoint` geometry or feature,
+/// or from feature collection of `Point` features keeping their order.
+pub fn read_multi_point<P: Ordinates<f64>>(geojson: &GeoJson) -> Result<Vec<P>> {
+    if let GeoJson::FeatureCollection(collection) = geojson {
+        return collection
+            .features
+            .iter()
+            .map(|feature| match feature.geometry.as_ref().map(|g| &g.value) {
+                Some(Value::Point(position)) => read_position(position),
+                _ => Err(invalid_format("Point feature is expected")),
+            })
+            .collect();
+    }
+
+    match geometry_value(geojson) {
+        Some(Value::MultiPoint(positions)) => positions.iter().map(read_position).collect(),
+        _ => Err(invalid_format("MultiPoint geometry is expected")),
+    }
+}
+
+/// Writes segments as feature collection of `LineString` features.
+pub fn write_segments<P: Ordinates<f64>>(segments: &[Vec<P>]) -> FeatureCollection {
+    let features = segments
+        .iter()
+        .enumerate()
+        .map(|(segment_index, segment)| {
+            let mut properties = JsonObject::new();
+            properties.insert("segment_index".to_string(), segment_index.into());
+            properties.insert("from_point_index".to_string(), segment_index.into());
+            properties.insert("to_point_index".to_string(), (segment_index + 1).into());
+
+            let positions = segment.iter().map(write_position).collect();
+
+            Feature {
+                geometry: Some(Geometry::new(Value::LineString(positions))),
+                properties: Some(properties),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    }
+}
+
+/// Splits `LineString` by `MultiPoint` and returns segments as feature collection.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::GeoJson;
+/// use polysplit::euclidean::Point;
+/// use polysplit::geojson::geojson_split;
+///
+/// let line_string: GeoJson = r#"{"type": "LineString", "coordinates": [[0, 0], [10, 0], [20, 0]]}"#.parse().unwrap();
+/// let points: GeoJson = r#"{"type": "MultiPoint", "coordinates": [[1, 1], [19, 1]]}"#.parse().unwrap();
+///
+/// let segments = geojson_split::<Point>(&line_string, &points, None).unwrap();
+///
+/// assert_eq!(segments.features.len(), 1);
+/// ```
+pub fn geojson_split<P>(
+    line_string: &GeoJson,
+    points: &GeoJson,
+    distance_threshold: Option<f64>,
+) -> Result<FeatureCollection>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let polyline: Vec<P> = read_line_string(line_string)?;
+    let points: Vec<P> = read_multi_point(points)?;
+    let segments = polyline_split(&polyline, &points, distance_threshold)?;
+
+    Ok(write_segments(&segments))
+}
+
+fn geometry_value(geojson: &GeoJson) -> Option<&Value> {
+    match geojson {
+        GeoJson::Geometry(geometry) => Some(&geometry.value),
+        GeoJson::Feature(feature) => feature.geometry.as_ref().map(|g| &g.value),
+        GeoJson::FeatureCollection(_) => None,
+    }
+}
+
+fn read_position<P: Ordinates<f64>>(position: &Position) -> Result<P> {
+    if position.len() < 2 {
+        return Err(invalid_format("position has less than two elements"));
+    }
+
+    Ok(P::from_ordinates(position[0], position[1], position.get(2).copied(), None))
+}
+
+fn write_position<P: Ordinates<f64>>(point: &P) -> Position {
+    let mut position = vec![point.x(), point.y()];
+    if let Some(z) = point.z() {
+        position.push(z);
+    }
+
+    position
+}
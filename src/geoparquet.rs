@@ -0,0 +1,131 @@
+//! [GeoParquet](https://geoparquet.org) files processing enabled by `geoparquet` feature.
+//!
+//! Files are read and written in record batches, geometry columns must have GeoArrow
+//! encoding, see [crate::geoarrow]. Segments column is written with `multilinestring`
+//! encoding keeping `crs` of the polylines column. Snappy, gzip, LZ4 and zstd compressed files
+//! are supported.
+use alloc::sync::Arc;
+use core::fmt::Debug;
+use std::io::Write;
+
+use arrow_array::{RecordBatch, RecordBatchReader};
+use arrow_schema::{ArrowError, Schema, SchemaRef};
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use parquet::file::metadata::KeyValue;
+use parquet::file::reader::ChunkReader;
+use serde_json::{json, Value};
+
+use crate::euclidean::Ordinates;
+use crate::geoarrow::{record_batch_split, split_schema};
+use crate::polysplit::{invalid_format, PolySplit, PolySplitError, Result};
+
+const GEO_KEY: &str = "geo";
+
+/// Opens the file for reading record batches, checks that `columns` are not WKB encoded
+/// and returns `geo` metadata of the file.
+pub fn read_geoparquet<R: ChunkReader + 'static>(
+    reader: R,
+    columns: &[&str],
+) -> Result<(ParquetRecordBatchReader, Option<Value>)> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader).map_err(parquet_error)?;
+
+    let geo = builder
+        .metadata()
+        .file_metadata()
+        .key_value_metadata()
+        .and_then(|metadata| metadata.iter().find(|kv| kv.key == GEO_KEY))
+        .and_then(|kv| kv.value.as_deref())
+        .map(serde_json::from_str::<Value>)
+        .transpose()
+        .map_err(|e| invalid_format(&format!("invalid geo metadata: {}", e)))?;
+
+    for column in columns {
+        let encoding = geo.as_ref().and_then(|geo| geo["columns"][column]["encoding"].as_str());
+        if encoding.is_some_and(|encoding| encoding.eq_ignore_ascii_case("WKB")) {
+            return Err(invalid_format(&format!("column {} is WKB encoded, GeoArrow encoding is expected", column)));
+        }
+    }
+
+    let reader = builder.build().map_err(parquet_error)?;
+    Ok((reader, geo))
+}
+
+/// Writes record batches with segments column as GeoParquet file, `crs` of the segments
+/// is written to `geo` metadata if it is defined.
+pub fn write_geoparquet<P: Ordinates<f64>>(
+    writer: impl Write + Send,
+    schema: SchemaRef,
+    segments_column: &str,
+    crs: Option<Value>,
+    batches: impl IntoIterator<Item = Result<RecordBatch>>,
+) -> Result<()> {
+    let geometry_type = match (P::HAS_Z, P::HAS_M) {
+        (true, true) => "MultiLineString ZM",
+        (true, false) => "MultiLineString Z",
+        (false, true) => "MultiLineString M",
+        (false, false) => "MultiLineString",
+    };
+
+    let mut column = json!({
+        "encoding": "multilinestring",
+        "geometry_types": [geometry_type],
+    });
+    if let Some(crs) = crs {
+        column["crs"] = crs;
+    }
+    let geo = json!({
+        "version": "1.1.0",
+        "primary_column": segments_column,
+        "columns": { segments_column: column },
+    });
+
+    // Metadata of the input file is replaced
+    let mut metadata = schema.metadata().clone();
+    metadata.remove(GEO_KEY);
+    let schema = Arc::new(Schema::new_with_metadata(schema.fields().clone(), metadata));
+
+    let mut writer = ArrowWriter::try_new(writer, schema.clone(), None).map_err(parquet_error)?;
+    for batch in batches {
+        let batch = RecordBatch::try_new(schema.clone(), batch?.columns().to_vec()).map_err(arrow_error)?;
+        writer.write(&batch).map_err(parquet_error)?;
+    }
+
+    writer.append_key_value_metadata(KeyValue::new(GEO_KEY.to_string(), geo.to_string()));
+    writer.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+/// Splits polylines by points row by row in record batches, polylines column is replaced
+/// by segments column of the same name and points column is removed.
+pub fn geoparquet_split<P, R>(
+    reader: R,
+    writer: impl Write + Send,
+    line_string_column: &str,
+    points_column: &str,
+    distance_threshold: Option<f64>,
+) -> Result<()>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+    R: ChunkReader + 'static,
+{
+    let (reader, geo) = read_geoparquet(reader, &[line_string_column, points_column])?;
+    let crs = geo.and_then(|mut geo| geo["columns"][line_string_column].get_mut("crs").map(Value::take));
+
+    let schema = split_schema::<P>(&reader.schema(), line_string_column, points_column)?;
+    let batches = reader.map(|batch| {
+        let batch = batch.map_err(arrow_error)?;
+        record_batch_split::<P>(&batch, line_string_column, points_column, distance_threshold)
+    });
+
+    write_geoparquet::<P>(writer, Arc::new(schema), line_string_column, crs, batches)
+}
+
+fn parquet_error(error: ParquetError) -> PolySplitError {
+    invalid_format(&error.to_string())
+}
+
+fn arrow_error(error: ArrowError) -> PolySplitError {
+    invalid_format(&error.to_string())
+}
@@ -0,0 +1,66 @@
+//! [PolySplit](crate::PolySplit) and [Interpolate](crate::Interpolate) implementations for [glam](https://docs.rs/glam) vectors
+//! enabled by `glam` feature.
+//!
+//! `Vec2` and `DVec2` are planar and `Vec3` and `DVec3` are spatial euclidean points.
+use glam::{DVec2, DVec3, Vec2, Vec3};
+
+use crate::euclidean::{impl_euclidean_polysplit, lerp, Euclidean};
+
+impl Euclidean<f32> for Vec2 {
+    fn position(&self) -> [f32; 3] {
+        [self.x, self.y, 0.0]
+    }
+
+    fn is_finite(&self) -> bool {
+        Vec2::is_finite(*self)
+    }
+
+    fn interpolate(&self, to: &Self, ratio: f32) -> Self {
+        Vec2::new(lerp(self.x, to.x, ratio), lerp(self.y, to.y, ratio))
+    }
+}
+
+impl Euclidean<f64> for DVec2 {
+    fn position(&self) -> [f64; 3] {
+        [self.x, self.y, 0.0]
+    }
+
+    fn is_finite(&self) -> bool {
+        DVec2::is_finite(*self)
+    }
+
+    fn interpolate(&self, to: &Self, ratio: f64) -> Self {
+        DVec2::new(lerp(self.x, to.x, ratio), lerp(self.y, to.y, ratio))
+    }
+}
+
+impl Euclidean<f32> for Vec3 {
+    fn position(&self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn is_finite(&self) -> bool {
+        Vec3::is_finite(*self)
+    }
+
+    fn interpolate(&self, to: &Self, ratio: f32) -> Self {
+        Vec3::new(lerp(self.x, to.x, ratio), lerp(self.y, to.y, ratio), lerp(self.z, to.z, ratio))
+    }
+}
+
+impl Euclidean<f64> for DVec3 {
+    fn position(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn is_finite(&self) -> bool {
+        DVec3::is_finite(*self)
+    }
+
+    fn interpolate(&self, to: &Self, ratio: f64) -> Self {
+        DVec3::new(lerp(self.x, to.x, ratio), lerp(self.y, to.y, ratio), lerp(self.z, to.z, ratio))
+    }
+}
+
+impl_euclidean_polysplit!(impl [] f32 => Vec2, Vec3);
+impl_euclidean_polysplit!(impl [] f64 => DVec2, DVec3);
@@ -0,0 +1,280 @@
+//! [GPX](https://www.topografix.com/gpx.asp) input and output enabled by `gpx` feature.
+//!
+//! Polyline is read from the first `<trk>` joining its track segments, points are read from
+//! `<wpt>` waypoints keeping their order. Longitude and latitude are mapped to `x` and `y`,
+//! elevation to `z` and time to `m` as seconds since Unix epoch if the point type has them,
+//! then every read point must have `<ele>` and `<time>` respectively.
+//! Segments are written as separate tracks.
+use core::fmt::Debug;
+use std::io::{BufRead, Write};
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::calendar::{civil_from_days, days_from_civil};
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, PolySplit, Result};
+
+/// Polyline and points read from GPX.
+#[derive(Debug, Clone)]
+pub struct Gpx<P> {
+    /// Points of the first track.
+    pub track: Vec<P>,
+    /// Waypoints.
+    pub waypoints: Vec<P>,
+}
+
+/// Reads the first track and waypoints.
+pub fn read_gpx<P: Ordinates<f64>>(reader: impl BufRead) -> Result<Gpx<P>> {
+    let mut reader = Reader::from_reader(reader);
+    reader.config_mut().trim_text(true);
+
+    let mut gpx = Gpx { track: Vec::new(), waypoints: Vec::new() };
+    let mut buffer = Vec::new();
+    let mut track_count = 0;
+    let mut point: Option<GpxPoint> = None;
+    let mut field: Option<Field> = None;
+
+    loop {
+        let event = reader
+            .read_event_into(&mut buffer)
+            .map_err(|e| invalid_format(&e.to_string()))?;
+
+        match event {
+            Event::Start(ref element) | Event::Empty(ref element) => {
+                let is_empty = matches!(event, Event::Empty(_));
+
+                match element.local_name().as_ref() {
+                    b"wpt" | b"trkpt" => {
+                        let new_point = GpxPoint::new(element)?;
+                        if is_empty {
+                            push_point(&mut gpx, element.local_name().as_ref(), new_point, track_count)?;
+                        } else {
+                            point = Some(new_point);
+                        }
+                    }
+                    b"ele" if point.is_some() && !is_empty => field = Some(Field::Elevation),
+                    b"time" if point.is_some() && !is_empty => field = Some(Field::Time),
+                    _ => {}
+                }
+            }
+            Event::Text(text) => {
+                let text = text.decode().map_err(|e| invalid_format(&e.to_string()))?;
+                if let (Some(point), Some(field)) = (point.as_mut(), field) {
+                    match field {
+                        Field::Elevation => point.elevation = Some(parse_number(&text)?),
+                        Field::Time => point.time = Some(parse_time(&text)?),
+                    }
+                }
+            }
+            Event::End(element) => match element.local_name().as_ref() {
+                name @ (b"wpt" | b"trkpt") => {
+                    if let Some(point) = point.take() {
+                        push_point(&mut gpx, name, point, track_count)?;
+                    }
+                }
+                b"ele" | b"time" => field = None,
+                b"trk" => track_count += 1,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+
+        buffer.clear();
+    }
+
+    Ok(gpx)
+}
+
+/// Writes segments as separate tracks numbered by segment index.
+pub fn write_tracks<P: Ordinates<f64>>(mut writer: impl Write, segments: &[Vec<P>]) -> Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<gpx version="1.1" creator="polysplit" xmlns="http://www.topografix.com/GPX/1/1">"#)?;
+
+    for (segment_index, segment) in segments.iter().enumerate() {
+        writeln!(writer, "  <trk>")?;
+        writeln!(writer, "    <number>{}</number>", segment_index)?;
+        writeln!(writer, "    <trkseg>")?;
+
+        for point in segment {
+            write!(writer, r#"      <trkpt lat="{}" lon="{}">"#, point.y(), point.x())?;
+            if let Some(z) = point.z() {
+                write!(writer, "<ele>{}</ele>", z)?;
+            }
+            if let Some(m) = point.m() {
+                write!(writer, "<time>{}</time>", format_time(m))?;
+            }
+            writeln!(writer, "</trkpt>")?;
+        }
+
+        writeln!(writer, "    </trkseg>")?;
+        writeln!(writer, "  </trk>")?;
+    }
+
+    writeln!(writer, "</gpx>")?;
+    Ok(())
+}
+
+/// Splits the first track by waypoints and writes segments as separate tracks.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::euclidean::PointZM;
+/// use polysplit::gpx::{gpx_split, read_gpx};
+///
+/// let gpx = r#"<gpx version="1.1">
+///     <wpt lat="1" lon="1"><ele>0</ele><time>2024-05-01T10:00:00Z</time></wpt>
+///     <wpt lat="1" lon="9"><ele>0</ele><time>2024-05-01T10:00:00Z</time></wpt>
+///     <trk><trkseg>
+///         <trkpt lat="0" lon="0"><ele>100</ele><time>2024-05-01T10:00:00Z</time></trkpt>
+///         <trkpt lat="0" lon="10"><ele>200</ele><time>2024-05-01T10:00:10Z</time></trkpt>
+///     </trkseg></trk>
+/// </gpx>"#;
+///
+/// let mut output = Vec::new();
+/// gpx_split::<PointZM>(gpx.as_bytes(), &mut output, None).unwrap();
+///
+/// let segments = read_gpx::<PointZM>(output.as_slice()).unwrap();
+/// assert_eq!(segments.track[0].2, 110.0);
+/// ```
+pub fn gpx_split<P>(reader: impl BufRead, writer: impl Write, distance_threshold: Option<f64>) -> Result<()>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let gpx: Gpx<P> = read_gpx(reader)?;
+    let segments = polyline_split(&gpx.track, &gpx.waypoints, distance_threshold)?;
+
+    write_tracks(writer, &segments)
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Elevation,
+    Time,
+}
+
+struct GpxPoint {
+    latitude: f64,
+    longitude: f64,
+    elevation: Option<f64>,
+    time: Option<f64>,
+}
+
+impl GpxPoint {
+    fn new(element: &BytesStart) -> Result<Self> {
+        let attribute = |name: &str| -> Result<f64> {
+            let attribute = element
+                .try_get_attribute(name)
+                .map_err(|e| invalid_format(&e.to_string()))?
+                .ok_or_else(|| invalid_format(&format!("{} attribute is expected", name)))?;
+            let value = attribute
+                .unescape_value()
+                .map_err(|e| invalid_format(&e.to_string()))?;
+
+            parse_number(&value)
+        };
+
+        Ok(GpxPoint {
+            latitude: attribute("lat")?,
+            longitude: attribute("lon")?,
+            elevation: None,
+            time: None,
+        })
+    }
+
+    /// Converts to the point type, elevation and time are required if the point type has them
+    /// rather than made up.
+    fn into_point<P: Ordinates<f64>>(self, name: &str) -> Result<P> {
+        if P::HAS_Z && self.elevation.is_none() {
+            return Err(invalid_format(&format!("{} must have <ele> for the point type with z", name)));
+        }
+        if P::HAS_M && self.time.is_none() {
+            return Err(invalid_format(&format!("{} must have <time> for the point type with m", name)));
+        }
+
+        Ok(P::from_ordinates(self.longitude, self.latitude, self.elevation, self.time))
+    }
+}
+
+fn push_point<P: Ordinates<f64>>(gpx: &mut Gpx<P>, name: &[u8], point: GpxPoint, track_count: usize) -> Result<()> {
+    match name {
+        b"wpt" => gpx.waypoints.push(point.into_point("wpt")?),
+        b"trkpt" if track_count == 0 => gpx.track.push(point.into_point("trkpt")?),
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn parse_number(value: &str) -> Result<f64> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| invalid_format(&format!("invalid number {}", value)))
+}
+
+/// Parses RFC 3339 time into seconds since Unix epoch.
+fn parse_time(value: &str) -> Result<f64> {
+    let error = || invalid_format(&format!("invalid time {}", value));
+    let number = |range: core::ops::Range<usize>| -> Result<i64> {
+        let digits = value.get(range).ok_or_else(error)?;
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(error());
+        }
+        digits.parse().map_err(|_| error())
+    };
+
+    let bytes = value.as_bytes();
+    if bytes.len() < 20 || bytes[4] != b'-' || bytes[7] != b'-' || !matches!(bytes[10], b'T' | b't' | b' ')
+        || bytes[13] != b':' || bytes[16] != b':' {
+        return Err(error());
+    }
+
+    let days = days_from_civil(number(0..4)?, number(5..7)?, number(8..10)?);
+    let mut seconds = (days * 86400 + number(11..13)? * 3600 + number(14..16)? * 60 + number(17..19)?) as f64;
+
+    let mut rest = &value[19..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction.bytes().take_while(|b| b.is_ascii_digit()).count();
+        seconds += format!("0.{}", &fraction[..len]).parse::<f64>().map_err(|_| error())?;
+        rest = &fraction[len..];
+    }
+
+    let offset = match rest.as_bytes() {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let len = value.len();
+            let offset = (number(len - 5..len - 3)? * 60 + number(len - 2..len)?) * 60;
+            if *sign == b'+' { offset } else { -offset }
+        }
+        _ => return Err(error()),
+    };
+
+    Ok(seconds - offset as f64)
+}
+
+/// Formats seconds since Unix epoch as RFC 3339 UTC time with milliseconds if they are not zero.
+fn format_time(seconds: f64) -> String {
+    let milliseconds = (seconds * 1000.0).round() as i64;
+    let (seconds, milliseconds) = (milliseconds.div_euclid(1000), milliseconds.rem_euclid(1000));
+    let (days, seconds) = (seconds.div_euclid(86400), seconds.rem_euclid(86400));
+    let (year, month, day) = civil_from_days(days);
+
+    let mut time = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+    if milliseconds != 0 {
+        time.push_str(&format!(".{:03}", milliseconds));
+    }
+    time.push('Z');
+
+    time
+}
@@ -0,0 +1,110 @@
+use crate::polysplit::{widening_mul, PolySplit, CutRatioResult, DistanceToSegmentResult, Fraction};
+
+/// Point with integer coordinates (for example nanometres or other fixed-point units).
+///
+/// All computations are exact: distances are squared euclidean distances presented as `i128`,
+/// so `distance_threshold` has to be squared as well and the split minimises the sum of squared
+/// distances. Projections not falling on integer coordinates are rounded to the nearest ones
+/// (halves are rounded away from the segment start).
+///
+/// Coordinates have to be strictly within `±2^62`, so the squared values do not overflow,
+/// points out of the range are reported as invalid coordinates. Totals of distances are
+/// checked, the split fails with `CannotSplit` error when the total does not fit into `i128`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Point(pub i64, pub i64);
+
+impl Point {
+    /// Coordinates are valid when they are strictly within `±LIMIT`.
+    const LIMIT: i64 = 1 << 62;
+
+    pub fn squared_distance_to(&self, to: &Point) -> i128 {
+        let dx = self.0 as i128 - to.0 as i128;
+        let dy = self.1 as i128 - to.1 as i128;
+
+        dx * dx + dy * dy
+    }
+}
+
+impl PolySplit<i128> for Point {
+    fn distance_to_point(&self, point: &Self) -> i128 {
+        self.squared_distance_to(point)
+    }
+
+    fn distance_to_segment(&self, s: (&Point, &Point)) -> DistanceToSegmentResult<Point, i128> {
+        let vx = s.1.0 as i128 - s.0.0 as i128;
+        let vy = s.1.1 as i128 - s.0.1 as i128;
+
+        let ux = self.0 as i128 - s.0.0 as i128;
+        let uy = self.1 as i128 - s.0.1 as i128;
+
+        // Projection ratio is `numerator / denominator`, the denominator is the same
+        // for all points projected to the segment, so numerators define the exact order
+        let numerator = ux * vx + uy * vy;
+        let denominator = vx * vx + vy * vy;
+
+        if denominator == 0 || numerator <= 0 {
+            DistanceToSegmentResult{
+                distance: self.squared_distance_to(s.0),
+                cut_point: *s.0,
+                cut_ratio: CutRatioResult::Begin,
+            }
+        } else if numerator >= denominator {
+            DistanceToSegmentResult{
+                distance: self.squared_distance_to(s.1),
+                cut_point: *s.1,
+                cut_ratio: CutRatioResult::End,
+            }
+        } else {
+            let x = s.0.0 as i128 + mul_div_round(vx, numerator, denominator);
+            let y = s.0.1 as i128 + mul_div_round(vy, numerator, denominator);
+            let cut_point = Point(x as i64, y as i64);
+
+            DistanceToSegmentResult{
+                distance: self.squared_distance_to(&cut_point),
+                cut_point,
+                cut_ratio: CutRatioResult::Fraction(Fraction { numerator, denominator }),
+            }
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        (-Self::LIMIT + 1..Self::LIMIT).contains(&self.0) && (-Self::LIMIT + 1..Self::LIMIT).contains(&self.1)
+    }
+
+    fn add_distances(a: i128, b: i128) -> Option<i128> {
+        a.checked_add(b)
+    }
+}
+
+/// Returns `value * numerator / denominator` rounded to the nearest integer,
+/// where `0 < numerator < denominator`.
+fn mul_div_round(value: i128, numerator: i128, denominator: i128) -> i128 {
+    let (hi, lo) = widening_mul(value.unsigned_abs(), numerator as u128);
+    let denominator = denominator as u128;
+
+    // Long division of 256-bit product, the quotient is less than `value`
+    let mut quotient: u128 = 0;
+    let mut remainder: u128 = 0;
+    for bit in (0..256).rev() {
+        let next = if bit >= 128 { (hi >> (bit - 128)) & 1 } else { (lo >> bit) & 1 };
+        remainder = (remainder << 1) | next;
+        quotient <<= 1;
+
+        if remainder >= denominator {
+            remainder -= denominator;
+            quotient |= 1;
+        }
+    }
+
+    if remainder >= denominator - remainder {
+        quotient += 1;
+    }
+
+    if value < 0 {
+        -(quotient as i128)
+    } else {
+        quotient as i128
+    }
+}
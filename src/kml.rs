@@ -0,0 +1,213 @@
+//! [KML](https://developers.google.com/kml/documentation/kmlreference) input and output
+//! enabled by `kml` feature.
+//!
+//! Polyline is read from the first `LineString` placemark and points from `Point` placemarks
+//! keeping their order. Longitude and latitude are mapped to `x` and `y`, altitude to `z`
+//! if the point type has it. Segments are written as `LineString` placemarks each having
+//! its own line style colour.
+use core::fmt::Debug;
+use std::io::{BufRead, Write};
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, PolySplit, Result};
+
+/// Polyline and points read from KML.
+#[derive(Debug, Clone)]
+pub struct Kml<P> {
+    /// Points of the first `LineString` placemark.
+    pub line_string: Vec<P>,
+    /// Points of `Point` placemarks.
+    pub points: Vec<P>,
+}
+
+/// Reads the first `LineString` placemark and `Point` placemarks.
+pub fn read_kml<P: Ordinates<f64>>(reader: impl BufRead) -> Result<Kml<P>> {
+    let mut reader = Reader::from_reader(reader);
+    reader.config_mut().trim_text(true);
+
+    let mut kml = Kml { line_string: Vec::new(), points: Vec::new() };
+    let mut buffer = Vec::new();
+    let mut has_line_string = false;
+    let mut geometry: Option<Geometry> = None;
+    let mut coordinates: Option<String> = None;
+
+    loop {
+        let event = reader
+            .read_event_into(&mut buffer)
+            .map_err(|e| invalid_format(&e.to_string()))?;
+
+        match event {
+            Event::Start(element) => match element.local_name().as_ref() {
+                b"LineString" => geometry = Some(Geometry::LineString),
+                b"Point" => geometry = Some(Geometry::Point),
+                b"coordinates" if geometry.is_some() => coordinates = Some(String::new()),
+                _ => {}
+            },
+            Event::Text(text) => {
+                if let Some(coordinates) = coordinates.as_mut() {
+                    coordinates.push_str(&text.decode().map_err(|e| invalid_format(&e.to_string()))?);
+                }
+            }
+            Event::CData(text) => {
+                if let Some(coordinates) = coordinates.as_mut() {
+                    coordinates.push_str(&text.decode().map_err(|e| invalid_format(&e.to_string()))?);
+                }
+            }
+            Event::End(element) => match element.local_name().as_ref() {
+                b"coordinates" => {
+                    let text = coordinates.take().unwrap_or_default();
+
+                    match geometry {
+                        Some(Geometry::LineString) if !has_line_string => {
+                            kml.line_string = parse_coordinates(&text)?;
+                            has_line_string = true;
+                        }
+                        Some(Geometry::Point) => {
+                            let mut points = parse_coordinates(&text)?;
+                            if points.len() != 1 {
+                                return Err(invalid_format("Point must have one coordinate"));
+                            }
+                            kml.points.append(&mut points);
+                        }
+                        _ => {}
+                    }
+                }
+                b"LineString" | b"Point" => geometry = None,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+
+        buffer.clear();
+    }
+
+    Ok(kml)
+}
+
+/// Writes segments as `LineString` placemarks with distinct colours.
+pub fn write_segments<P: Ordinates<f64>>(mut writer: impl Write, segments: &[Vec<P>]) -> Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#)?;
+    writeln!(writer, "  <Document>")?;
+
+    for segment_index in 0..segments.len() {
+        writeln!(writer, r#"    <Style id="segment-{}">"#, segment_index)?;
+        writeln!(writer, "      <LineStyle><color>{}</color><width>4</width></LineStyle>", color(segment_index))?;
+        writeln!(writer, "    </Style>")?;
+    }
+
+    for (segment_index, segment) in segments.iter().enumerate() {
+        writeln!(writer, "    <Placemark>")?;
+        writeln!(writer, "      <name>Segment {}</name>", segment_index)?;
+        writeln!(writer, "      <styleUrl>#segment-{}</styleUrl>", segment_index)?;
+        writeln!(writer, "      <LineString>")?;
+        if P::HAS_Z {
+            writeln!(writer, "        <altitudeMode>absolute</altitudeMode>")?;
+        }
+
+        write!(writer, "        <coordinates>")?;
+        for (point_index, point) in segment.iter().enumerate() {
+            if point_index > 0 {
+                write!(writer, " ")?;
+            }
+
+            write!(writer, "{},{}", point.x(), point.y())?;
+            if let Some(z) = point.z() {
+                write!(writer, ",{}", z)?;
+            }
+        }
+        writeln!(writer, "</coordinates>")?;
+
+        writeln!(writer, "      </LineString>")?;
+        writeln!(writer, "    </Placemark>")?;
+    }
+
+    writeln!(writer, "  </Document>")?;
+    writeln!(writer, "</kml>")?;
+    Ok(())
+}
+
+/// Splits the first `LineString` placemark by `Point` placemarks and writes segments
+/// as styled placemarks.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::euclidean::Point;
+/// use polysplit::kml::kml_split;
+///
+/// let kml = r#"<kml xmlns="http://www.opengis.net/kml/2.2"><Document>
+///     <Placemark><LineString><coordinates>0,0 10,0 20,0</coordinates></LineString></Placemark>
+///     <Placemark><Point><coordinates>1,1</coordinates></Point></Placemark>
+///     <Placemark><Point><coordinates>10,-1</coordinates></Point></Placemark>
+///     <Placemark><Point><coordinates>19,1</coordinates></Point></Placemark>
+/// </Document></kml>"#;
+///
+/// let mut output = Vec::new();
+/// kml_split::<Point>(kml.as_bytes(), &mut output, None).unwrap();
+///
+/// let output = String::from_utf8(output).unwrap();
+/// assert!(output.contains("<coordinates>1,0 10,0</coordinates>"));
+/// assert!(output.contains("<coordinates>10,0 19,0</coordinates>"));
+/// ```
+pub fn kml_split<P>(reader: impl BufRead, writer: impl Write, distance_threshold: Option<f64>) -> Result<()>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let kml: Kml<P> = read_kml(reader)?;
+    let segments = polyline_split(&kml.line_string, &kml.points, distance_threshold)?;
+
+    write_segments(writer, &segments)
+}
+
+#[derive(Clone, Copy)]
+enum Geometry {
+    LineString,
+    Point,
+}
+
+/// Parses `longitude,latitude[,altitude]` tuples separated by whitespaces.
+fn parse_coordinates<P: Ordinates<f64>>(text: &str) -> Result<Vec<P>> {
+    text.split_whitespace()
+        .map(|tuple| {
+            let values = tuple
+                .split(',')
+                .map(|value| value.parse::<f64>())
+                .collect::<core::result::Result<Vec<_>, _>>()
+                .map_err(|_| invalid_format(&format!("invalid coordinates {}", tuple)))?;
+
+            match values[..] {
+                [x, y] => Ok(P::from_ordinates(x, y, None, None)),
+                [x, y, z] => Ok(P::from_ordinates(x, y, Some(z), None)),
+                _ => Err(invalid_format(&format!("invalid coordinates {}", tuple))),
+            }
+        })
+        .collect()
+}
+
+/// Returns opaque KML `aabbggrr` colour, hues of the consecutive indexes are spread
+/// by the golden angle so that they are distinct.
+fn color(index: usize) -> String {
+    const GOLDEN_ANGLE: f64 = 137.507_764;
+    const SATURATION: f64 = 0.85;
+    const VALUE: f64 = 0.95;
+
+    let hue = (index as f64 * GOLDEN_ANGLE) % 360.0 / 60.0;
+    let chroma = VALUE * SATURATION;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let channel = |value: f64| ((value + VALUE - chroma) * 255.0).round() as u8;
+    format!("ff{:02x}{:02x}{:02x}", channel(b), channel(g), channel(r))
+}
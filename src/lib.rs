@@ -2,18 +2,66 @@
 //!
 //! `polysplit` crate implements the algorithm allowing to split polylines
 //! into segments by the defined list of points not necessary belonging to the polyline.
+//!
+//! The crate is `no_std` compatible with `alloc` when default `std` feature is disabled.
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+
+extern crate alloc;
+
 mod polysplit;
-pub use crate::polysplit::{CutRatioResult, DistanceToSegmentResult, PolySplit};
-pub use crate::polysplit::{PolySplitErrorKind, PolySplitError, Result};
-pub use crate::polysplit::polyline_split;
+pub use crate::polysplit::{CutRatioResult, DistanceToSegmentResult, Fraction, Interpolate, PolySplit};
+pub use crate::polysplit::{InputIndex, PolySplitErrorKind, PolySplitError, Result};
+pub use crate::polysplit::{polyline_split, polyline_split_with_tolerance};
 
+pub mod anisotropic;
 pub mod euclidean;
+pub mod integer;
+pub mod taxicab;
+
+#[cfg(any(feature = "gpx", feature = "shapefile"))]
+mod calendar;
+
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "encoded-polyline")]
+pub mod encoded_polyline;
+#[cfg(feature = "flatgeobuf")]
+pub mod flatgeobuf;
+#[cfg(feature = "geo-types")]
+pub mod geo_types;
+#[cfg(feature = "geoarrow")]
+pub mod geoarrow;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+#[cfg(feature = "geoparquet")]
+pub mod geoparquet;
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "gpx")]
+pub mod gpx;
+#[cfg(feature = "kml")]
+pub mod kml;
+#[cfg(feature = "mint")]
+mod mint;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "rstar")]
+pub mod rstar;
+#[cfg(feature = "shapefile")]
+pub mod shapefile;
+#[cfg(feature = "wkb")]
+pub mod wkb;
+#[cfg(feature = "wkt")]
+pub mod wkt;
 
 #[cfg(test)]
 mod tests {
-    use crate::polysplit::polyline_split;
+    use crate::polysplit::{polyline_split, polyline_split_with_tolerance, CutRatioResult, PolySplit};
     use crate::euclidean::Point;
 
+    #[allow(clippy::ptr_arg)]
     fn is_equal(actual: &Vec<Vec<Point>>, expected: &Vec<Vec<(f64, f64)>>) -> bool {
         const EPS: f64 = 1E-16;
 
@@ -132,6 +180,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn tests_distance_threshold() {
         let tests = vec![
             (
@@ -152,6 +201,1361 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn tests_plain_types() {
+        let polyline = [(0.0, 0.0), (10.0, 0.0), (20.0, 0.0)];
+        let points = [(1.0, 1.0), (19.0, 1.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![(1.0, 0.0), (10.0, 0.0), (19.0, 0.0)]]);
+
+        let polyline = [[0.0, 0.0], [10.0, 0.0], [20.0, 0.0]];
+        let points = [[1.0, 1.0], [19.0, 1.0]];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![[1.0, 0.0], [10.0, 0.0], [19.0, 0.0]]]);
+
+        let polyline = [[0.0, 0.0, 0.0], [10.0, 0.0, 10.0]];
+        let points = [[0.0, 0.0, 10.0], [10.0, 0.0, 10.0]];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![[5.0, 0.0, 5.0], [10.0, 0.0, 10.0]]]);
+
+        let polyline = [(0.0f32, 0.0f32, 0.0f32), (10.0, 0.0, 10.0)];
+        let points = [(0.0, 0.0, 10.0), (10.0, 0.0, 10.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![(5.0, 0.0, 5.0), (10.0, 0.0, 10.0)]]);
+
+        let point: Point = [1.0, 2.0].into();
+        assert_eq!(<(f64, f64)>::from(point), (1.0, 2.0));
+    }
+
+    #[test]
+    fn tests_interpolate() {
+        use crate::euclidean::PointZM;
+        use crate::polysplit::Interpolate;
+
+        let segment = (&Point(0.0, 0.0), &Point(3.0, 4.0));
+        assert_eq!(Point::segment_length(segment), 5.0);
+        assert!(Point::interpolate(segment, 0.2).distance_to(&Point(0.6, 0.8)) < 1E-12);
+        assert!(Point::interpolate_at_distance(segment, 2.5).distance_to(&Point(1.5, 2.0)) < 1E-12);
+        assert!(Point::interpolate_at_distance(segment, 10.0).distance_to(&Point(6.0, 8.0)) < 1E-12);
+
+        // Degenerate segment
+        let segment = (&Point(1.0, 1.0), &Point(1.0, 1.0));
+        assert!(Point::interpolate_at_distance(segment, 1.0).distance_to(&Point(1.0, 1.0)) < 1E-12);
+
+        let segment = (&PointZM(0.0f32, 0.0, 10.0, 100.0), &PointZM(10.0, 0.0, 20.0, 200.0));
+        let actual = PointZM::interpolate_at_distance(segment, 5.0);
+        assert_eq!((actual.0, actual.1, actual.2, actual.3), (5.0, 0.0, 15.0, 150.0));
+
+        assert_eq!(<(f64, f64)>::interpolate((&(0.0, 0.0), &(0.0, 10.0)), 0.5), (0.0, 5.0));
+    }
+
+    #[test]
+    fn tests_tolerance() {
+        let tests = [
+            // Projection close to the vertex is snapped to it
+            (
+                vec![(0.0, 0.0), (10.0, 0.0), (20.0, 0.0)],
+                vec![(9.9999, 1.0), (19.0, 1.0)],
+                vec![vec![(10.0, 0.0), (19.0, 0.0)]],
+            ),
+
+            (
+                vec![(0.0, 0.0), (10.0, 0.0), (20.0, 0.0)],
+                vec![(0.0001, 1.0), (10.0001, 1.0)],
+                vec![vec![(0.0, 0.0), (10.0, 0.0)]],
+            ),
+
+            // Short segment is degenerate
+            (
+                vec![(0.0, 0.0), (10.0, 0.0), (10.0, 0.0001), (20.0, 0.0001)],
+                vec![(1.0, 1.0), (19.0, 1.0)],
+                vec![vec![(1.0, 0.0), (10.0, 0.0), (10.0, 0.0001), (19.0, 0.0001)]],
+            ),
+        ];
+
+        for (polyline, points, expected) in &tests {
+            let polyline: Vec<Point> = polyline.iter().map(|p| Point(p.0, p.1)).collect();
+            let points: Vec<Point> = points.iter().map(|p| Point(p.0, p.1)).collect();
+
+            let result = polyline_split_with_tolerance(&polyline, &points, None, 0.001);
+            let actual = result.unwrap();
+
+            assert!(is_equal(&actual, expected), "polyline={:?}, points={:?}, actual={:?}", polyline, points, actual);
+        }
+
+        use crate::polysplit::Interpolate;
+        use crate::taxicab::Point as TaxicabPoint;
+
+        // Tolerance below the default precision of coordinates
+        let polyline = vec![Point(0.0, 0.0), Point(1e-10, 0.0)];
+        let points = vec![Point(2e-11, 1e-11), Point(8e-11, 1e-11)];
+        let actual = polyline_split_with_tolerance(&polyline, &points, None, 1e-12).unwrap();
+        assert!(is_equal(&actual, &vec![vec![(2e-11, 0.0), (8e-11, 0.0)]]), "actual={:?}", actual);
+
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert!(is_equal(&actual, &vec![vec![(0.0, 0.0), (0.0, 0.0)]]), "actual={:?}", actual);
+
+        let segment = (&Point(0.0, 0.0), &Point(1e-10, 0.0));
+        assert_eq!(Point::interpolate_at_distance(segment, 5e-11).0, 0.0);
+        assert_eq!(Point::interpolate_at_distance_with_tolerance(segment, 5e-11, 1e-12).0, 5e-11);
+
+        let polyline: Vec<TaxicabPoint> = vec![TaxicabPoint(0.0, 0.0), TaxicabPoint(1e-10, 0.0)];
+        let points = vec![TaxicabPoint(2e-11, 1e-11), TaxicabPoint(8e-11, 1e-11)];
+        let actual = polyline_split_with_tolerance(&polyline, &points, None, 1e-12).unwrap();
+        assert!((actual[0][0].0 - 2e-11).abs() < 1e-16 && (actual[0][1].0 - 8e-11).abs() < 1e-16);
+    }
+
+    #[test]
+    fn tests_f32() {
+        let polyline: Vec<Point<f32>> = vec![Point(0.0, 0.0), Point(10.0, 0.0), Point(20.0, 0.0)];
+        let points: Vec<Point<f32>> = vec![Point(1.0, 1.0), Point(19.0, 1.0)];
+
+        let actual = polyline_split(&polyline, &points, Some(2.0f32)).unwrap();
+        let expected = [(1.0f32, 0.0f32), (10.0, 0.0), (19.0, 0.0)];
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].len(), expected.len());
+        for (actual_point, expected_point) in actual[0].iter().zip(expected) {
+            assert!(actual_point.distance_to(&Point(expected_point.0, expected_point.1)) < 1E-6);
+        }
+    }
+
+    #[test]
+    fn tests_interpolated_ordinates() {
+        use crate::euclidean::{PointZ, PointZM, Point3D};
+
+        let polyline: Vec<PointZM> = vec![PointZM(0.0, 0.0, 0.0, 0.0), PointZM(10.0, 0.0, 100.0, 10.0)];
+        let points = vec![PointZM(2.0, 1.0, 0.0, 0.0), PointZM(8.0, 1.0, 0.0, 0.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+
+        assert_eq!(actual.len(), 1);
+        let (begin, end) = (actual[0][0], actual[0][1]);
+        assert!((begin.0 - 2.0).abs() < 1E-12 && (begin.2 - 20.0).abs() < 1E-12 && (begin.3 - 2.0).abs() < 1E-12);
+        assert!((end.0 - 8.0).abs() < 1E-12 && (end.2 - 80.0).abs() < 1E-12 && (end.3 - 8.0).abs() < 1E-12);
+
+        // Elevation is taken into account only by spatial points
+        let polyline: Vec<PointZ> = vec![PointZ(0.0, 0.0, 0.0), PointZ(10.0, 0.0, 10.0)];
+        let points = vec![PointZ(0.0, 0.0, 10.0), PointZ(10.0, 0.0, 10.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert!(actual[0][0].distance_to(&PointZ(0.0, 0.0, 0.0)) < 1E-12);
+        assert!((actual[0][0].2 - 0.0).abs() < 1E-12);
+
+        let polyline: Vec<Point3D> = vec![Point3D(0.0, 0.0, 0.0), Point3D(10.0, 0.0, 10.0)];
+        let points = vec![Point3D(0.0, 0.0, 10.0), Point3D(10.0, 0.0, 10.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert!(actual[0][0].distance_to(&Point3D(5.0, 0.0, 5.0)) < 1E-12);
+    }
+
+    #[test]
+    fn tests_taxicab() {
+        use crate::taxicab::Point as TaxicabPoint;
+
+        let polyline = vec![TaxicabPoint(0.0, 0.0), TaxicabPoint(10.0, 0.0), TaxicabPoint(10.0, 10.0)];
+        let points = vec![TaxicabPoint(3.0, 2.0), TaxicabPoint(12.0, 4.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].len(), 3);
+        assert!(actual[0][0].distance_to(&TaxicabPoint(3.0, 0.0)) < 1E-12);
+        assert!(actual[0][2].distance_to(&TaxicabPoint(10.0, 4.0)) < 1E-12);
+
+        // Distance is the same along the whole diagonal segment, so the start is chosen
+        let result = TaxicabPoint(10.0f64, 0.0).distance_to_segment((&TaxicabPoint(0.0, 0.0), &TaxicabPoint(10.0, 10.0)));
+        assert!(matches!(result.cut_ratio, CutRatioResult::Begin));
+        assert!((result.distance - 10.0).abs() < 1E-12);
+    }
+
+    #[test]
+    fn tests_anisotropic() {
+        use crate::anisotropic::Metric;
+        use crate::polysplit::PolySplitErrorKind;
+
+        let weighted: Metric = Metric::new(1.0, 3.0).unwrap();
+
+        let polyline = vec![weighted.point(0.0, 0.0), weighted.point(10.0, 10.0)];
+        let points = vec![weighted.point(10.0, 0.0), weighted.point(10.0, 10.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+
+        assert_eq!(actual.len(), 1);
+        assert!(actual[0][0].distance_to(&weighted.point(2.5, 2.5)) < 1E-12);
+        assert!(actual[0][1].distance_to(&weighted.point(10.0, 10.0)) < 1E-12);
+
+        let polyline = vec![weighted.point(0.0, 0.0), weighted.point(1e-10, 0.0)];
+        let points = vec![weighted.point(2e-11, 1e-11), weighted.point(8e-11, 1e-11)];
+        let actual = polyline_split_with_tolerance(&polyline, &points, None, 1e-12).unwrap();
+        assert!((actual[0][0].x - 2e-11).abs() < 1e-16 && (actual[0][1].x - 8e-11).abs() < 1e-16);
+
+        for (wx, wy) in [(0.0, 1.0), (1.0, -1.0), (f64::NAN, 1.0), (1.0, f64::INFINITY)] {
+            assert_eq!(Metric::new(wx, wy).unwrap_err().kind(), &PolySplitErrorKind::InvalidMetric);
+        }
+
+        // Metric entry point rejects points of other metrics
+        let actual = weighted.polyline_split_with_tolerance(&polyline, &points, None, 1e-12).unwrap();
+        assert_eq!(actual.len(), 1);
+
+        let other = Metric::new(1.0, 100.0).unwrap();
+        let polyline = vec![weighted.point(0.0, 0.0), weighted.point(1.0, 1.0)];
+        for points in [vec![other.point(0.0, 1.0)], vec![weighted.point(0.0, 1.0), other.point(1.0, 0.0)]] {
+            let error = weighted.polyline_split(&polyline, &points, None).unwrap_err();
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidMetric);
+        }
+        let error = other.polyline_split(&polyline, &[other.point(0.0, 1.0)], None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidMetric);
+
+        // Deserialized metric is validated by split
+        #[cfg(feature = "serde")]
+        {
+            use crate::anisotropic::Point as AnisotropicPoint;
+            use crate::polysplit::InputIndex;
+
+            let json = r#"{"x":1.0,"y":0.0,"metric":{"wx":0.0,"wy":1.0}}"#;
+            let invalid: AnisotropicPoint = serde_json::from_str(json).unwrap();
+            let polyline = vec![weighted.point(0.0, 0.0), invalid];
+            let error = polyline_split(&polyline, &points, None).unwrap_err();
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidCoordinate(InputIndex::Polyline(1)));
+        }
+    }
+
+    #[test]
+    fn tests_integer() {
+        use crate::integer::Point as IntPoint;
+        use crate::polysplit::{Fraction, InputIndex, PolySplitErrorKind};
+
+        let tests = [
+            (
+                vec![IntPoint(0, 0), IntPoint(10, 0), IntPoint(20, 0)],
+                vec![IntPoint(1, 1), IntPoint(19, 1)],
+                vec![vec![IntPoint(1, 0), IntPoint(10, 0), IntPoint(19, 0)]],
+            ),
+
+            // Projection (1.5, 0.5) is rounded
+            (
+                vec![IntPoint(0, 0), IntPoint(3, 1)],
+                vec![IntPoint(0, 0), IntPoint(1, 2)],
+                vec![vec![IntPoint(0, 0), IntPoint(2, 1)]],
+            ),
+
+            // Projection (-1.5, -0.5) is rounded
+            (
+                vec![IntPoint(0, 0), IntPoint(-3, -1)],
+                vec![IntPoint(0, 0), IntPoint(-1, -2)],
+                vec![vec![IntPoint(0, 0), IntPoint(-2, -1)]],
+            ),
+
+            // Large coordinates requiring more than 128 bits for intermediate values
+            (
+                vec![IntPoint(0, 0), IntPoint(4_000_000_000_000_000_000, 0)],
+                vec![IntPoint(1_000_000_000_000_000_001, 7), IntPoint(3_000_000_000_000_000_003, -7)],
+                vec![vec![IntPoint(1_000_000_000_000_000_001, 0), IntPoint(3_000_000_000_000_000_003, 0)]],
+            ),
+
+            // Cut ratios differing less than `f64` precision
+            (
+                vec![IntPoint(0, 0), IntPoint(1 << 61, 0)],
+                vec![IntPoint((1 << 60) + 1, 1), IntPoint((1 << 60) + 2, 1)],
+                vec![vec![IntPoint((1 << 60) + 1, 0), IntPoint((1 << 60) + 2, 0)]],
+            ),
+        ];
+
+        for (polyline, points, expected) in &tests {
+            let actual = polyline_split(polyline, points, None).unwrap();
+            assert_eq!(&actual, expected);
+        }
+
+        let segment = (&IntPoint(0, 0), &IntPoint(1 << 61, 0));
+        let a = IntPoint((1 << 60) + 1, 1).distance_to_segment(segment).cut_ratio;
+        let b = IntPoint((1 << 60) + 2, 1).distance_to_segment(segment).cut_ratio;
+        assert!(a < b);
+        let fraction = |n, d| CutRatioResult::Fraction(Fraction::new(n, d).unwrap());
+        assert!(fraction(-1, 3) < fraction(1, 3));
+        assert!(fraction(1, 2) == fraction(i128::MAX / 2, i128::MAX - 1));
+        assert!(fraction(1, -2) < fraction(1, 3) && fraction(1, -2) == fraction(-1, 2));
+        assert!(Fraction::new(1, 0).is_none() && Fraction::new(i128::MIN, -1).is_none());
+
+        // Equality agrees with ordering, exact and rounded ratios are ordered by variant
+        for (a, b) in [(CutRatioResult::Medium(0.5), fraction(1, 2)), (CutRatioResult::Medium(0.9), fraction(1, 3))] {
+            assert!(a != b && a < b && a.cmp(&b) == core::cmp::Ordering::Less);
+        }
+        assert!(CutRatioResult::Begin < CutRatioResult::Medium(0.0) && fraction(1, 1) < CutRatioResult::End);
+
+        // Total of squared distances does not fit into `i128`
+        let a = (1 << 62) - 1;
+        let polyline = vec![IntPoint(-a, -a), IntPoint(-a + 1, -a)];
+        let points = vec![IntPoint(a - 1, a - 1), IntPoint(a - 1, a - 1)];
+        let error = polyline_split(&polyline, &points, None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::CannotSplit);
+
+        let a = 1 << 62;
+        let polyline = vec![IntPoint(-a, -a), IntPoint(-a + 1, -a)];
+        let error = polyline_split(&polyline, &points, None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidCoordinate(InputIndex::Polyline(0)));
+    }
+
+    #[test]
+    #[cfg(feature = "geo-types")]
+    fn tests_geo_types() {
+        use geo_types::{line_string, Coord, MultiPoint, Point as GeoPoint};
+        use crate::geo_types::line_string_split;
+        use crate::polysplit::Interpolate;
+
+        let line_string = line_string![(x: 0.0, y: 0.0), (x: 10.0, y: 0.0), (x: 20.0, y: 0.0)];
+        let points = MultiPoint(vec![GeoPoint::new(1.0, 1.0), GeoPoint::new(10.0, -1.0), GeoPoint::new(19.0, 1.0)]);
+
+        let actual = line_string_split(&line_string, &points, None).unwrap();
+        let expected = vec![
+            line_string![(x: 1.0, y: 0.0), (x: 10.0, y: 0.0)],
+            line_string![(x: 10.0, y: 0.0), (x: 19.0, y: 0.0)],
+        ];
+        assert_eq!(actual.0, expected);
+
+        let polyline = vec![GeoPoint::new(0.0f32, 0.0), GeoPoint::new(10.0, 0.0)];
+        let points = vec![GeoPoint::new(1.0, 1.0), GeoPoint::new(9.0, 1.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![GeoPoint::new(1.0, 0.0), GeoPoint::new(9.0, 0.0)]]);
+
+        let polyline = vec![Coord { x: 0.0, y: 0.0 }, Coord { x: 10.0, y: 0.0 }];
+        let points = vec![Coord { x: 1.0, y: 1.0 }, Coord { x: 9.0, y: 1.0 }];
+        let actual = polyline_split(&polyline, &points, Some(2.0)).unwrap();
+        assert_eq!(actual, vec![vec![Coord { x: 1.0, y: 0.0 }, Coord { x: 9.0, y: 0.0 }]]);
+
+        let segment = (&GeoPoint::new(0.0, 0.0), &GeoPoint::new(6.0, 8.0));
+        assert_eq!(GeoPoint::interpolate(segment, 0.5), GeoPoint::new(3.0, 4.0));
+        assert_eq!(GeoPoint::interpolate_at_distance(segment, 5.0), GeoPoint::new(3.0, 4.0));
+        let segment = (&Coord { x: 0.0, y: 0.0 }, &Coord { x: 0.0, y: 10.0 });
+        assert_eq!(Coord::interpolate(segment, 0.5), Coord { x: 0.0, y: 5.0 });
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn tests_serde() {
+        use crate::euclidean::PointZ;
+        use crate::polysplit::{DistanceToSegmentResult, PolySplitErrorKind};
+
+        let result = Point(5.0, 1.0).distance_to_segment((&Point(0.0, 0.0), &Point(10.0, 0.0)));
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(json, r#"{"cut_ratio":{"medium":0.5},"cut_point":[5.0,0.0],"distance":1.0}"#);
+
+        let result: DistanceToSegmentResult<Point, f64> = serde_json::from_str(&json).unwrap();
+        assert!(result.cut_ratio == CutRatioResult::Medium(0.5));
+
+        assert_eq!(serde_json::to_string(&CutRatioResult::Begin).unwrap(), r#""begin""#);
+        assert_eq!(serde_json::to_string(&CutRatioResult::End).unwrap(), r#""end""#);
+
+        let fraction = CutRatioResult::Fraction(crate::Fraction::new(1, 2).unwrap());
+        assert_eq!(serde_json::to_string(&fraction).unwrap(), r#"{"fraction":[1,2]}"#);
+        assert!(serde_json::from_str::<CutRatioResult>(r#"{"fraction":[1,0]}"#).is_err());
+        assert_eq!(serde_json::to_string(&PointZ(1.0, 2.0, 3.0)).unwrap(), "[1.0,2.0,3.0]");
+
+        let polyline: Vec<Point> = vec![Point(0.0, 0.0)];
+        let error = polyline_split(&polyline, &polyline, None).unwrap_err();
+        let json = serde_json::to_string(&error).unwrap();
+        assert_eq!(json, r#"{"kind":"invalid_polyline","message":"polyline has not enough points"}"#);
+
+        let error: crate::PolySplitError = serde_json::from_str(&json).unwrap();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidPolyline);
+    }
+
+    #[test]
+    #[cfg(feature = "nalgebra")]
+    fn tests_nalgebra() {
+        use nalgebra::{Point2, Point3};
+
+        let polyline = vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), Point2::new(20.0, 0.0)];
+        let points = vec![Point2::new(1.0, 1.0), Point2::new(19.0, 1.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![Point2::new(1.0, 0.0), Point2::new(10.0, 0.0), Point2::new(19.0, 0.0)]]);
+
+        let polyline = vec![Point3::new(0.0f32, 0.0, 0.0), Point3::new(10.0, 0.0, 10.0)];
+        let points = vec![Point3::new(0.0, 0.0, 10.0), Point3::new(10.0, 0.0, 10.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![Point3::new(5.0, 0.0, 5.0), Point3::new(10.0, 0.0, 10.0)]]);
+    }
+
+    #[test]
+    #[cfg(feature = "glam")]
+    fn tests_glam() {
+        use glam::{DVec2, Vec3};
+        use crate::polysplit::Interpolate;
+
+        let polyline = vec![DVec2::new(0.0, 0.0), DVec2::new(10.0, 0.0), DVec2::new(20.0, 0.0)];
+        let points = vec![DVec2::new(1.0, 1.0), DVec2::new(19.0, 1.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![DVec2::new(1.0, 0.0), DVec2::new(10.0, 0.0), DVec2::new(19.0, 0.0)]]);
+
+        let polyline = vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 10.0)];
+        let points = vec![Vec3::new(0.0, 0.0, 10.0), Vec3::new(10.0, 0.0, 10.0)];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![Vec3::new(5.0, 0.0, 5.0), Vec3::new(10.0, 0.0, 10.0)]]);
+
+        let segment = (&Vec3::new(0.0, 0.0, 0.0), &Vec3::new(0.0, 3.0, 4.0));
+        assert_eq!(Vec3::interpolate_at_distance(segment, 10.0), Vec3::new(0.0, 6.0, 8.0));
+        assert!(!Vec3::new(f32::NAN, 0.0, 0.0).is_valid());
+    }
+
+    #[test]
+    #[cfg(feature = "mint")]
+    fn tests_mint() {
+        use mint::Point2;
+        use crate::polysplit::Interpolate;
+
+        let polyline = vec![Point2 { x: 0.0, y: 0.0 }, Point2 { x: 10.0, y: 0.0 }];
+        let points = vec![Point2 { x: 1.0, y: 1.0 }, Point2 { x: 9.0, y: -1.0 }];
+        let actual = polyline_split(&polyline, &points, None).unwrap();
+        assert_eq!(actual, vec![vec![Point2 { x: 1.0, y: 0.0 }, Point2 { x: 9.0, y: 0.0 }]]);
+
+        let segment = (&Point2 { x: 0.0, y: 0.0 }, &Point2 { x: 10.0, y: 20.0 });
+        assert_eq!(Point2::interpolate(segment, 0.5), Point2 { x: 5.0, y: 10.0 });
+    }
+
+    #[test]
+    #[cfg(feature = "rstar")]
+    fn tests_rstar() {
+        use rstar::primitives::{GeomWithData, Line};
+
+        use crate::rstar::{polyline_split_indexed, segment_tree};
+
+        let polyline = [(100.0, 140.0), (140.0, 200.0), (260.0, 300.0), (300.0, 240.0), (400.0, 220.0), (380.0, 260.0), (420.0, 340.0), (460.0, 340.0), (500.0, 320.0), (580.0, 280.0), (600.0, 240.0), (620.0, 180.0), (580.0, 160.0), (520.0, 140.0), (480.0, 100.0), (480.0, 60.0), (520.0, 40.0), (560.0, 40.0), (620.0, 60.0), (660.0, 80.0), (780.0, 180.0), (780.0, 300.0), (640.0, 360.0)];
+        let points = [(180.0, 200.0), (260.0, 240.0), (340.0, 260.0), (500.0, 280.0), (540.0, 160.0), (520.0, 60.0), (700.0, 160.0), (680.0, 380.0)];
+
+        let polyline: Vec<Point> = polyline.iter().map(|p| Point(p.0, p.1)).collect();
+        let points: Vec<Point> = points.iter().map(|p| Point(p.0, p.1)).collect();
+        let tree = segment_tree(&polyline);
+
+        for distance_threshold in [60.0, 100.0, 1000.0] {
+            let expected = polyline_split(&polyline, &points, Some(distance_threshold)).unwrap();
+            let actual = polyline_split_indexed(&polyline, &tree, &points, distance_threshold).unwrap();
+            let expected: Vec<Vec<(f64, f64)>> = expected.iter().map(|s| s.iter().map(|p| (p.0, p.1)).collect()).collect();
+
+            assert!(is_equal(&actual, &expected), "actual={:?}, expected={:?}", actual, expected);
+        }
+
+        assert!(polyline_split_indexed(&polyline, &tree, &points, 10.0).is_err());
+
+        // Existing tree of other segment objects
+        let lines: Vec<_> = polyline
+            .windows(2)
+            .enumerate()
+            .map(|(index, s)| GeomWithData::new(Line::new([s[0].0, s[0].1], [s[1].0, s[1].1]), index))
+            .collect();
+        let tree = rstar::RTree::bulk_load(lines);
+        for distance_threshold in [60.0, 100.0, 1000.0] {
+            let expected = polyline_split(&polyline, &points, Some(distance_threshold)).unwrap();
+            let actual = polyline_split_indexed(&polyline, &tree, &points, distance_threshold).unwrap();
+            let expected: Vec<Vec<(f64, f64)>> = expected.iter().map(|s| s.iter().map(|p| (p.0, p.1)).collect()).collect();
+
+            assert!(is_equal(&actual, &expected), "actual={:?}, expected={:?}", actual, expected);
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    use proptest::strategy::{Just, Strategy};
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn tests_known_split(split in crate::proptest::known_split(1..20, 2..10)) {
+            let actual = polyline_split(&split.polyline, &split.points, None).unwrap();
+
+            proptest::prop_assert_eq!(actual.len(), split.segments.len());
+            for (actual_segment, expected_segment) in actual.iter().zip(&split.segments) {
+                proptest::prop_assert_eq!(actual_segment.len(), expected_segment.len());
+                for (actual_point, expected_point) in actual_segment.iter().zip(expected_segment) {
+                    proptest::prop_assert!(actual_point.distance_to(expected_point) < 1E-9);
+                }
+            }
+        }
+
+        #[test]
+        fn tests_noisy_points(
+            (polyline, points) in crate::proptest::polyline(-100.0..100.0, 2..10)
+                .prop_flat_map(|polyline| (Just(polyline.clone()), crate::proptest::noisy_points(polyline, 2..10, 1.0)))
+        ) {
+            const NOISE: f64 = 1.0;
+            const EPS: f64 = 1e-9;
+
+            let length = |points: &[Point]| points.windows(2).map(|w| w[0].distance_to(&w[1])).sum::<f64>();
+
+            // Position of the point along the polyline, the point is expected to lie on it
+            let along = |point: &Point| polyline
+                .windows(2)
+                .enumerate()
+                .find(|(_, w)| point.distance_to_segment((&w[0], &w[1])).distance < EPS)
+                .map(|(index, w)| length(&polyline[..index + 1]) + w[0].distance_to(point));
+
+            // Every point has a cut point within the noise bound
+            let actual = polyline_split(&polyline, &points, Some(NOISE + EPS)).unwrap();
+            proptest::prop_assert_eq!(actual.len(), points.len() - 1);
+
+            let mut cut_points: Vec<Point> = actual.iter().map(|s| s[0]).collect();
+            cut_points.push(actual[actual.len() - 1][actual[actual.len() - 1].len() - 1]);
+
+            for (cut_point, point) in cut_points.iter().zip(&points) {
+                proptest::prop_assert!(cut_point.distance_to(point) <= NOISE + EPS, "cut_point={:?}, point={:?}", cut_point, point);
+            }
+
+            // Consecutive segments share endpoints
+            for (segment, next) in actual.iter().zip(&actual[1..]) {
+                let (end, start) = (segment[segment.len() - 1], next[0]);
+                proptest::prop_assert!(end.0 == start.0 && end.1 == start.1, "end={:?}, start={:?}", end, start);
+            }
+
+            // Segments cover the polyline between the first and the last cut points
+            let first = along(&cut_points[0]).unwrap();
+            let last = along(&cut_points[cut_points.len() - 1]).unwrap();
+            let total: f64 = actual.iter().map(|s| length(s)).sum();
+            proptest::prop_assert!((total - (last - first)).abs() < 1e-6, "total={}, covered={}", total, last - first);
+        }
+    }
+
+    #[test]
+    fn tests_invalid_coordinates() {
+        use crate::polysplit::{InputIndex, PolySplitErrorKind};
+
+        let polyline = vec![Point(0.0, 0.0), Point(10.0, f64::NAN), Point(20.0, 0.0)];
+        let points = vec![Point(1.0, 1.0), Point(19.0, 1.0)];
+        let error = polyline_split(&polyline, &points, None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidCoordinate(InputIndex::Polyline(1)));
+
+        let polyline = vec![Point(0.0, 0.0), Point(10.0, 0.0), Point(20.0, 0.0)];
+        let points = vec![Point(1.0, 1.0), Point(f64::INFINITY, 1.0)];
+        let error = polyline_split(&polyline, &points, None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidCoordinate(InputIndex::Points(1)));
+
+        let polyline = vec![(0.0, 0.0, f64::NAN), (10.0, 0.0, 0.0)];
+        let points = vec![(1.0, 1.0, 0.0), (9.0, 1.0, 0.0)];
+        let error = polyline_split(&polyline, &points, None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidCoordinate(InputIndex::Polyline(0)));
+
+        // NaN threshold does not panic
+        let polyline = vec![Point(0.0, 0.0), Point(10.0, 0.0)];
+        let points = vec![Point(1.0, 1.0), Point(9.0, 1.0)];
+        assert!(polyline_split(&polyline, &points, Some(f64::NAN)).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "geoarrow")]
+    fn tests_geoarrow() {
+        use std::sync::Arc;
+
+        use arrow_array::{Array, FixedSizeListArray, Float64Array, ListArray, RecordBatch, StringArray, StructArray};
+        use arrow_buffer::{NullBuffer, OffsetBuffer};
+        use arrow_schema::{DataType, Field, Fields, Schema};
+
+        use crate::euclidean::{PointM, PointZ};
+        use crate::geoarrow::{geoarrow_split, read_line_strings, read_multi_line_strings, record_batch_split};
+        use crate::polysplit::PolySplitErrorKind;
+
+        // Separated coordinates with null second row
+        let fields = Fields::from(vec![Field::new("x", DataType::Float64, false), Field::new("y", DataType::Float64, false)]);
+        let coordinates = StructArray::new(
+            fields.clone(),
+            vec![
+                Arc::new(Float64Array::from(vec![0.0, 10.0, 20.0, 0.0, 0.0])),
+                Arc::new(Float64Array::from(vec![0.0, 0.0, 0.0, 0.0, 10.0])),
+            ],
+            None,
+        );
+        let vertex_field = Arc::new(Field::new("vertices", DataType::Struct(fields), false));
+        let line_strings = ListArray::new(
+            vertex_field.clone(),
+            OffsetBuffer::from_lengths([3, 0, 2]),
+            Arc::new(coordinates),
+            Some(NullBuffer::from(vec![true, false, true])),
+        );
+
+        // Interleaved coordinates
+        let coordinates = FixedSizeListArray::new(
+            Arc::new(Field::new("xy", DataType::Float64, false)),
+            2,
+            Arc::new(Float64Array::from(vec![1.0, 1.0, 10.0, -1.0, 19.0, 1.0, 1.0, 1.0, 1.0, 9.0])),
+            None,
+        );
+        let points = ListArray::new(
+            Arc::new(Field::new("vertices", coordinates.data_type().clone(), false)),
+            OffsetBuffer::from_lengths([3, 0, 2]),
+            Arc::new(coordinates),
+            None,
+        );
+
+        let segments = geoarrow_split::<Point>(&line_strings, &points, None).unwrap();
+        assert_eq!(segments.null_count(), 1);
+
+        let actual = read_multi_line_strings::<Point>(&segments).unwrap();
+        assert!(is_equal(actual[0].as_ref().unwrap(), &vec![vec![(1.0, 0.0), (10.0, 0.0)], vec![(10.0, 0.0), (19.0, 0.0)]]));
+        assert!(actual[1].is_none());
+        assert!(is_equal(actual[2].as_ref().unwrap(), &vec![vec![(0.0, 1.0), (0.0, 9.0)]]));
+
+        // Sliced arrays keep their rows
+        let actual = read_line_strings::<Point>(&line_strings.slice(2, 1)).unwrap();
+        assert!(is_equal(&vec![actual[0].clone().unwrap()], &vec![vec![(0.0, 0.0), (0.0, 10.0)]]));
+        let actual = read_line_strings::<Point>(&points.slice(2, 1)).unwrap();
+        assert!(is_equal(&vec![actual[0].clone().unwrap()], &vec![vec![(1.0, 1.0), (1.0, 9.0)]]));
+        let actual = read_multi_line_strings::<Point>(&segments.slice(2, 1)).unwrap();
+        assert!(is_equal(actual[0].as_ref().unwrap(), &vec![vec![(0.0, 1.0), (0.0, 9.0)]]));
+
+        // Z is interpolated and M is zero
+        let segments = geoarrow_split::<PointZ>(&line_strings, &points, None).unwrap();
+        let actual = read_multi_line_strings::<PointM>(&segments).unwrap();
+        assert_eq!(actual[0].as_ref().unwrap()[0][0].2, 0.0);
+
+        // Record batch columns
+        let ids: Arc<dyn Array> = Arc::new(StringArray::from(vec!["a", "b", "c"]));
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("geometry", line_strings.data_type().clone(), true),
+            Field::new("stops", points.data_type().clone(), true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![ids, Arc::new(line_strings.clone()), Arc::new(points.clone())],
+        ).unwrap();
+
+        let actual = record_batch_split::<Point>(&batch, "geometry", "stops", None).unwrap();
+        assert_eq!(actual.num_columns(), 2);
+        assert_eq!(
+            actual.schema().field(1).metadata()["ARROW:extension:name"],
+            "geoarrow.multilinestring",
+        );
+
+        let error = geoarrow_split::<Point>(&line_strings, &points.slice(0, 2), None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat);
+        let error = geoarrow_split::<Point>(&points, &line_strings, Some(0.5)).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::PointFarAway);
+        assert!(error.message.starts_with("row 0:"));
+        let error = record_batch_split::<Point>(&batch, "geometry", "points", None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat);
+
+        #[cfg(feature = "geoparquet")]
+        {
+            use parquet::arrow::ArrowWriter;
+            use parquet::basic::Compression;
+            use parquet::file::metadata::KeyValue;
+            use parquet::file::properties::WriterProperties;
+            use parquet::file::reader::{FileReader, SerializedFileReader};
+
+            use crate::euclidean::{PointM, PointZ, PointZM};
+            use crate::geoparquet::{geoparquet_split, read_geoparquet};
+
+            let path = std::env::temp_dir().join(format!("polysplit-geoparquet-{}.parquet", std::process::id()));
+            let write = |compression: Compression| {
+                let properties = WriterProperties::builder().set_compression(compression).build();
+                let file = std::fs::File::create(&path).unwrap();
+                let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties)).unwrap();
+                writer.write(&batch).unwrap();
+                writer.append_key_value_metadata(KeyValue::new(
+                    "geo".to_string(),
+                    r#"{"version": "1.1.0", "primary_column": "geometry", "columns": {
+                        "geometry": {"encoding": "linestring", "geometry_types": [], "crs": {"id": {"authority": "EPSG", "code": 3857}}},
+                        "stops": {"encoding": "multipoint", "geometry_types": []}
+                    }}"#.to_string(),
+                ));
+                writer.close().unwrap();
+            };
+
+            for compression in [
+                Compression::UNCOMPRESSED,
+                Compression::SNAPPY,
+                Compression::GZIP(Default::default()),
+                Compression::LZ4_RAW,
+                Compression::ZSTD(Default::default()),
+            ] {
+                write(compression);
+                let metadata = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap().metadata().clone();
+                assert_eq!(metadata.row_group(0).column(0).compression(), compression);
+
+                let mut output = Vec::new();
+                geoparquet_split::<Point, _>(std::fs::File::open(&path).unwrap(), &mut output, "geometry", "stops", None).unwrap();
+                std::fs::write(&path, output).unwrap();
+
+                let (reader, geo) = read_geoparquet(std::fs::File::open(&path).unwrap(), &["geometry"]).unwrap();
+                let geo = geo.unwrap();
+                assert_eq!(geo["columns"]["geometry"]["encoding"], "multilinestring");
+                assert_eq!(geo["columns"]["geometry"]["geometry_types"][0], "MultiLineString");
+                assert_eq!(geo["columns"]["geometry"]["crs"]["id"]["code"], 3857);
+
+                let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+                let actual = read_multi_line_strings::<Point>(batches[0].column_by_name("geometry").unwrap()).unwrap();
+                assert!(is_equal(actual[2].as_ref().unwrap(), &vec![vec![(0.0, 1.0), (0.0, 9.0)]]), "{:?}", compression);
+            }
+
+            // Geometry types have the ordinates of the point type
+            fn geometry_type<P>(path: &std::path::Path) -> serde_json::Value
+            where
+                P: PolySplit<f64> + crate::euclidean::Ordinates<f64> + core::fmt::Debug,
+            {
+                let output = path.with_extension("segments.parquet");
+                geoparquet_split::<P, _>(std::fs::File::open(path).unwrap(), std::fs::File::create(&output).unwrap(), "geometry", "stops", None).unwrap();
+                let (_, geo) = read_geoparquet(std::fs::File::open(&output).unwrap(), &["geometry"]).unwrap();
+                std::fs::remove_file(&output).unwrap();
+                geo.unwrap()["columns"]["geometry"]["geometry_types"][0].take()
+            }
+
+            write(Compression::UNCOMPRESSED);
+            assert_eq!(geometry_type::<PointM>(&path), "MultiLineString M");
+            assert_eq!(geometry_type::<PointZ>(&path), "MultiLineString Z");
+            assert_eq!(geometry_type::<PointZM>(&path), "MultiLineString ZM");
+
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "geojson")]
+    fn tests_geojson() {
+        use geojson::GeoJson;
+        use crate::euclidean::PointZ;
+        use crate::geojson::geojson_split;
+        use crate::polysplit::PolySplitErrorKind;
+
+        let line_string: GeoJson = r#"{
+            "type": "Feature",
+            "properties": {},
+            "geometry": {"type": "LineString", "coordinates": [[0, 0, 0], [10, 0, 100], [20, 0, 200]]}
+        }"#.parse().unwrap();
+        let points: GeoJson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [10, -1]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [19, 1]}}
+            ]
+        }"#.parse().unwrap();
+
+        let actual = geojson_split::<PointZ>(&line_string, &points, None).unwrap();
+        let expected: GeoJson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": {"segment_index": 0, "from_point_index": 0, "to_point_index": 1},
+                    "geometry": {"type": "LineString", "coordinates": [[1, 0, 10], [10, 0, 100]]}
+                },
+                {
+                    "type": "Feature",
+                    "properties": {"segment_index": 1, "from_point_index": 1, "to_point_index": 2},
+                    "geometry": {"type": "LineString", "coordinates": [[10, 0, 100], [19, 0, 190]]}
+                }
+            ]
+        }"#.parse().unwrap();
+        assert_eq!(GeoJson::FeatureCollection(actual), expected);
+
+        let error = geojson_split::<Point>(&points, &points, None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat);
+    }
+
+    #[test]
+    #[cfg(feature = "csv")]
+    fn tests_csv() {
+        use crate::csv::{csv_split, read_groups};
+        use crate::euclidean::PointZ;
+        use crate::polysplit::PolySplitErrorKind;
+
+        let polylines = "id,sequence,x,y,z\n\
+            b,1,0,0,0\n\
+            a,3,20,0,20\n\
+            a,1,0,0,0\n\
+            b,2,0,10,10\n\
+            a,2,10,0,10\n";
+
+        let points = "sequence,id,x,y\n\
+            2,a,19,1\n\
+            1,b,1,1\n\
+            1,a,1,1\n\
+            2,b,1,9\n";
+
+        let groups = read_groups::<PointZ>(polylines.as_bytes()).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["a"].iter().map(|p| p.2).collect::<Vec<_>>(), vec![0.0, 10.0, 20.0]);
+
+        let mut output = Vec::new();
+        csv_split::<PointZ>(polylines.as_bytes(), points.as_bytes(), &mut output, None).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "polyline_id,segment_index,vertex_index,x,y,z\n\
+            a,0,0,1,0,1\n\
+            a,0,1,10,0,10\n\
+            a,0,2,19,0,19\n\
+            b,0,0,0,1,1\n\
+            b,0,1,0,9,9\n",
+        );
+
+        // Polyline without points is skipped
+        let points = "id,sequence,x,y\na,1,1,1\na,2,19,1\n";
+        let mut output = Vec::new();
+        csv_split::<Point>(polylines.as_bytes(), points.as_bytes(), &mut output, None).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "polyline_id,segment_index,vertex_index,x,y\n\
+            a,0,0,1,0\n\
+            a,0,1,10,0\n\
+            a,0,2,19,0\n",
+        );
+
+        // Polyline with a single point
+        let points = "id,sequence,x,y\na,1,1,1\n";
+        let error = csv_split::<Point>(polylines.as_bytes(), points.as_bytes(), Vec::new(), None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidPoints);
+        assert!(error.message.starts_with("polyline a:"));
+
+        for polylines in ["id,x,y\na,0,0\n", "id,sequence,x,y\na,1,0,x\n", "id,sequence,x,y\na,1.5,0,0\n", "id,sequence,x,y\na,1,0\n"] {
+            let error = read_groups::<Point>(polylines.as_bytes()).unwrap_err();
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat, "{}", polylines);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "encoded-polyline")]
+    fn tests_encoded_polyline() {
+        use crate::euclidean::PointZ;
+        use crate::encoded_polyline::{decode, encode, encoded_polyline_split};
+        use crate::polysplit::PolySplitErrorKind;
+
+        // Example of the format description
+        let points: Vec<Point> = decode("_p~iF~ps|U_ulLnnqC_mqNvxq`@", 5).unwrap();
+        assert!(is_equal(&vec![points.clone()], &vec![vec![(-120.2, 38.5), (-120.95, 40.7), (-126.453, 43.252)]]));
+        assert_eq!(encode(&points, 5).unwrap(), "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+
+        let points = [Point(13.388860, 52.517037), Point(-0.000001, -0.000001)];
+        let encoded = encode(&points, 6).unwrap();
+        let actual: Vec<Point> = decode(&encoded, 6).unwrap();
+        assert!(is_equal(&vec![actual], &vec![vec![(13.388860, 52.517037), (-0.000001, -0.000001)]]));
+
+        let points: Vec<PointZ> = decode("??", 5).unwrap();
+        assert_eq!((points[0].0, points[0].1, points[0].2), (0.0, 0.0, 0.0));
+
+        let points = [Point(0.5, 1.0), Point(1.5, 1.0)];
+        let encoded = encode(&[Point(0.0, 0.0), Point(2.0, 0.0)], 6).unwrap();
+        let actual = encoded_polyline_split(&encoded, &points, None, 6).unwrap();
+        assert_eq!(actual, vec![encode(&[Point(0.5, 0.0), Point(1.5, 0.0)], 6).unwrap()]);
+
+        for encoded in ["_p~iF", "_p~iF~ps|U_", "_p~iF ps|U", "~~~~~~~~~~~~~~~?"] {
+            let error = decode::<Point>(encoded, 5).unwrap_err();
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat, "{}", encoded);
+        }
+
+        // Values and their sums overflowing `i64`
+        for encoded in ["~~~~~~~~~~~~^".to_string(), "~~~~~~~~~~~^".repeat(40)] {
+            let error = decode::<Point>(&encoded, 5).unwrap_err();
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat, "{}", encoded);
+        }
+
+        let points = [Point(4e18, -4e18), Point(-4e18, 4e18)];
+        let actual: Vec<Point> = decode(&encode(&points, 0).unwrap(), 0).unwrap();
+        assert_eq!((actual[1].0, actual[1].1), (-4e18, 4e18));
+
+        for points in [vec![Point(0.0, 1e300)], vec![Point(f64::NAN, 0.0)], vec![Point(0.0, -9e18), Point(0.0, 9e18)]] {
+            let error = encode(&points, 0).unwrap_err();
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat, "{:?}", points);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "flatgeobuf")]
+    fn tests_flatgeobuf() {
+        use std::collections::BTreeMap;
+
+        use crate::euclidean::PointZM;
+        use crate::flatgeobuf::{flatgeobuf_split, LineStringReader, SegmentWriter};
+        use crate::polysplit::PolySplitErrorKind;
+
+        let mut polylines = SegmentWriter::new(Vec::new()).unwrap();
+        polylines.write_segments("a", &[vec![PointZM(0.0, 0.0, 0.0, 0.0), PointZM(10.0, 0.0, 10.0, 100.0)]]).unwrap();
+        polylines.write_segments("b", &[vec![PointZM(0.0, 0.0, 0.0, 0.0), PointZM(0.0, 10.0, 10.0, 100.0)]]).unwrap();
+        let polylines = polylines.into_inner();
+
+        let points = BTreeMap::from([
+            ("a".to_string(), vec![PointZM(1.0, 1.0, 0.0, 0.0), PointZM(5.0, -1.0, 0.0, 0.0), PointZM(9.0, 1.0, 0.0, 0.0)]),
+            ("b".to_string(), vec![PointZM(1.0, 1.0, 0.0, 0.0), PointZM(1.0, 9.0, 0.0, 0.0)]),
+        ]);
+
+        let mut segments = Vec::new();
+        flatgeobuf_split(polylines.as_slice(), &points, &mut segments, "SOURCE_ID", None).unwrap();
+
+        let mut reader = LineStringReader::new(segments.as_slice(), "SOURCE_ID").unwrap();
+        let mut actual = Vec::new();
+        while let Some((id, segment)) = reader.read_line_string::<PointZM>().unwrap() {
+            actual.push((id, segment.iter().map(|p| (p.0, p.1, p.2, p.3)).collect::<Vec<_>>()));
+        }
+        assert_eq!(actual, vec![
+            ("a".to_string(), vec![(1.0, 0.0, 1.0, 10.0), (5.0, 0.0, 5.0, 50.0)]),
+            ("a".to_string(), vec![(5.0, 0.0, 5.0, 50.0), (9.0, 0.0, 9.0, 90.0)]),
+            ("b".to_string(), vec![(0.0, 1.0, 1.0, 10.0), (0.0, 9.0, 9.0, 90.0)]),
+        ]);
+
+        // Segment index can be used as key as well
+        let mut reader = LineStringReader::new(segments.as_slice(), "SEGMENT").unwrap();
+        let keys: Vec<String> = std::iter::from_fn(|| reader.read_line_string::<Point>().unwrap().map(|(id, _)| id)).collect();
+        assert_eq!(keys, vec!["0", "1", "0"]);
+
+        // Header table offset out of the buffer is not verified
+        let mut corrupted = segments.clone();
+        corrupted[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        for error in [
+            LineStringReader::new(segments.as_slice(), "ID").err().unwrap(),
+            LineStringReader::new(&segments[1..], "SOURCE_ID").err().unwrap(),
+            LineStringReader::new(corrupted.as_slice(), "SOURCE_ID").err().unwrap(),
+        ] {
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat);
+        }
+
+        // Truncated last feature
+        let mut reader = LineStringReader::new(&segments[..segments.len() - 1], "SOURCE_ID").unwrap();
+        let error = loop {
+            if let Err(error) = reader.read_line_string::<Point>() {
+                break error;
+            }
+        };
+        assert_eq!(error.kind(), &PolySplitErrorKind::Io);
+
+        let error = flatgeobuf_split(polylines.as_slice(), &BTreeMap::<String, Vec<PointZM>>::new(), Vec::new(), "SOURCE_ID", None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidPoints);
+
+        // File written by GDAL: spatial index is skipped and key is read, but polygons are rejected
+        let countries: &[u8] = include_bytes!("../tests/data/flatgeobuf/countries.fgb");
+        let mut reader = LineStringReader::new(countries, "name").unwrap();
+        let error = reader.read_line_string::<Point>().unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat);
+        assert_eq!(error.message, "polyline Antarctica: LineString geometry is expected, got 6");
+
+        // Size prefix larger than the input is not allocated up front
+        let mut huge = segments[..8].to_vec();
+        huge.extend_from_slice(&u32::MAX.to_le_bytes());
+        let error = LineStringReader::new(huge.as_slice(), "SOURCE_ID").err().unwrap();
+        assert_eq!(error.kind(), &PolySplitErrorKind::Io);
+    }
+
+    #[test]
+    #[cfg(feature = "gpx")]
+    fn tests_gpx() {
+        use crate::euclidean::{PointM, PointZ, PointZM};
+        use crate::gpx::{gpx_split, read_gpx, write_tracks};
+        use crate::polysplit::PolySplitErrorKind;
+
+        let gpx = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1">
+                <wpt lat="1" lon="1"><name>A</name><ele>0</ele><time>2024-02-29T00:00:00Z</time></wpt>
+                <wpt lat="-1" lon="10"><name>B</name><ele>0</ele><time>2024-02-29T00:00:00Z</time></wpt>
+                <wpt lat="1" lon="19"><ele>0</ele><time>2024-02-29T00:00:00Z</time></wpt>
+                <trk>
+                    <name>Route</name>
+                    <trkseg>
+                        <trkpt lat="0" lon="0"><ele>100</ele><time>2024-02-28T23:59:50Z</time></trkpt>
+                        <trkpt lat="0" lon="10"><ele>200</ele><time>2024-02-29T00:00:00.5+00:00</time></trkpt>
+                    </trkseg>
+                    <trkseg>
+                        <trkpt lat="0" lon="20"><ele>300</ele><time>2024-02-29T02:00:10.5+02:00</time></trkpt>
+                    </trkseg>
+                </trk>
+                <trk><trkseg><trkpt lat="50" lon="50"/></trkseg></trk>
+            </gpx>"#;
+
+        let input: crate::gpx::Gpx<PointZM> = read_gpx(gpx.as_bytes()).unwrap();
+        assert_eq!(input.track.len(), 3);
+        assert_eq!(input.waypoints.len(), 3);
+        assert_eq!((input.track[1].0, input.track[1].1, input.track[1].2), (10.0, 0.0, 200.0));
+        assert_eq!(input.track[1].3 - input.track[0].3, 10.5);
+        assert_eq!(input.track[2].3 - input.track[1].3, 10.0);
+
+        let mut output = Vec::new();
+        gpx_split::<PointZM>(gpx.as_bytes(), &mut output, None).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(r#"<trkpt lat="0" lon="1"><ele>110</ele><time>2024-02-28T23:59:51.050Z</time></trkpt>"#), "{}", output);
+        assert!(output.contains(r#"<trkpt lat="0" lon="10"><ele>200</ele><time>2024-02-29T00:00:00.500Z</time></trkpt>"#), "{}", output);
+        assert!(output.contains("<number>1</number>"), "{}", output);
+
+        let segments: crate::gpx::Gpx<PointZM> = read_gpx(output.as_bytes()).unwrap();
+        assert_eq!(segments.track.len(), 2);
+
+        // Points without elevation keep time only
+        let mut output = Vec::new();
+        write_tracks(&mut output, &[vec![PointM(1.0, 2.0, -1.0)]]).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(r#"<trkpt lat="2" lon="1"><time>1969-12-31T23:59:59Z</time></trkpt>"#), "{}", output);
+
+        for gpx in [
+            r#"<gpx><wpt lon="1"/></gpx>"#,
+            r#"<gpx><wpt lat="x" lon="1"/></gpx>"#,
+            r#"<gpx><wpt lat="1" lon="1"><time>2024-02-29</time></wpt></gpx>"#,
+            r#"<gpx><wpt lat="1" lon="1"></gpx>"#,
+        ] {
+            let error = read_gpx::<Point>(gpx.as_bytes()).unwrap_err();
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat, "{}", gpx);
+        }
+
+        // Missing elevation and time are not made up
+        let track = r#"<gpx><trk><trkseg><trkpt lat="0" lon="0"><ele>1</ele></trkpt></trkseg></trk></gpx>"#;
+        assert_eq!(read_gpx::<PointZ>(track.as_bytes()).unwrap().track[0].2, 1.0);
+        let error = read_gpx::<PointZM>(track.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat);
+        assert!(error.message.contains("<time>"), "{}", error.message);
+
+        let waypoint = r#"<gpx><wpt lat="0" lon="0"><time>2024-02-29T00:00:00Z</time></wpt></gpx>"#;
+        assert_eq!(read_gpx::<PointM>(waypoint.as_bytes()).unwrap().waypoints.len(), 1);
+        let error = read_gpx::<PointZ>(waypoint.as_bytes()).unwrap_err();
+        assert!(error.message.contains("<ele>"), "{}", error.message);
+    }
+
+    #[test]
+    #[cfg(feature = "kml")]
+    fn tests_kml() {
+        use crate::euclidean::PointZ;
+        use crate::kml::{kml_split, read_kml, write_segments};
+        use crate::polysplit::PolySplitErrorKind;
+
+        let kml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <kml xmlns="http://www.opengis.net/kml/2.2">
+                <Document>
+                    <Folder>
+                        <Placemark>
+                            <name>Route</name>
+                            <LineString>
+                                <coordinates>
+                                    0,0,100 10,0,200
+                                    20,0,300
+                                </coordinates>
+                            </LineString>
+                        </Placemark>
+                        <Placemark><LineString><coordinates>50,50 60,60</coordinates></LineString></Placemark>
+                    </Folder>
+                    <Placemark><name>A</name><Point><coordinates>1,1</coordinates></Point></Placemark>
+                    <Placemark><name>B</name><Point><coordinates>10,-1,0</coordinates></Point></Placemark>
+                    <Placemark><name>C</name><Point><coordinates><![CDATA[19,1]]></coordinates></Point></Placemark>
+                </Document>
+            </kml>"#;
+
+        let input = read_kml::<PointZ>(kml.as_bytes()).unwrap();
+        assert_eq!(input.line_string.iter().map(|p| (p.0, p.2)).collect::<Vec<_>>(), vec![(0.0, 100.0), (10.0, 200.0), (20.0, 300.0)]);
+        assert_eq!(input.points.iter().map(|p| (p.0, p.1)).collect::<Vec<_>>(), vec![(1.0, 1.0), (10.0, -1.0), (19.0, 1.0)]);
+
+        let mut output = Vec::new();
+        kml_split::<PointZ>(kml.as_bytes(), &mut output, None).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("<coordinates>1,0,110 10,0,200</coordinates>"), "{}", output);
+        assert!(output.contains("<coordinates>10,0,200 19,0,290</coordinates>"), "{}", output);
+        assert!(output.contains("<styleUrl>#segment-1</styleUrl>"), "{}", output);
+
+        let segments = read_kml::<Point>(output.as_bytes()).unwrap();
+        assert_eq!(segments.line_string.len(), 2);
+
+        // Colours of the pieces are distinct
+        let mut output = Vec::new();
+        write_segments(&mut output, &vec![vec![Point(0.0, 0.0), Point(1.0, 1.0)]; 12]).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let colors: std::collections::HashSet<&str> = output
+            .split("<color>")
+            .skip(1)
+            .map(|s| &s[..8])
+            .collect();
+        assert_eq!(colors.len(), 12);
+        assert!(colors.iter().all(|c| c.starts_with("ff")));
+
+        for kml in [
+            "<kml><Placemark><Point><coordinates>1</coordinates></Point></Placemark></kml>",
+            "<kml><Placemark><Point><coordinates>1,x</coordinates></Point></Placemark></kml>",
+            "<kml><Placemark><Point><coordinates>1,1 2,2</coordinates></Point></Placemark></kml>",
+            "<kml><Placemark><Point><coordinates>1,1</Point></Placemark></kml>",
+        ] {
+            let error = read_kml::<Point>(kml.as_bytes()).unwrap_err();
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat, "{}", kml);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "shapefile")]
+    fn tests_shapefile() {
+        use std::collections::BTreeMap;
+
+        use crate::euclidean::{PointZ, PointZM};
+        use crate::polysplit::PolySplitErrorKind;
+        use crate::shapefile::{read_points, read_polylines, shapefile_split, write_segments};
+
+        fn write(path: &std::path::Path, shapes: &BTreeMap<String, Vec<Vec<PointZ>>>) {
+            let mut files = [Vec::new(), Vec::new(), Vec::new()];
+            let [shp, shx, dbf] = &mut files;
+            write_segments(shp, shx, dbf, shapes).unwrap();
+
+            for (extension, content) in ["shp", "shx", "dbf"].iter().zip(files) {
+                std::fs::write(path.with_extension(extension), content).unwrap();
+            }
+        }
+
+        let directory = std::env::temp_dir().join(format!("polysplit-shapefile-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let polylines = BTreeMap::from([
+            ("a".to_string(), vec![vec![PointZ(0.0, 0.0, 0.0), PointZ(10.0, 0.0, 10.0), PointZ(20.0, 0.0, 20.0)]]),
+            ("bb".to_string(), vec![vec![PointZ(0.0, 0.0, 0.0), PointZ(0.0, 10.0, 10.0)]]),
+        ]);
+        write(&directory.join("polylines"), &polylines);
+
+        let actual: BTreeMap<String, Vec<PointZ>> = read_polylines(
+            std::fs::File::open(directory.join("polylines.shp")).unwrap(),
+            std::fs::File::open(directory.join("polylines.dbf")).unwrap(),
+            "source_id",
+        ).unwrap();
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual["a"].iter().map(|p| (p.0, p.2)).collect::<Vec<_>>(), vec![(0.0, 0.0), (10.0, 10.0), (20.0, 20.0)]);
+
+        // MultiPoint shapes with the same DBF records
+        let mut shp = Vec::new();
+        let records = [vec![(1.0, 1.0), (19.0, -1.0)], vec![(1.0, 1.0), (1.0, 9.0)]];
+        for (index, points) in records.iter().enumerate() {
+            let mut content = 8i32.to_le_bytes().to_vec();
+            content.extend([0.0f64; 4].iter().flat_map(|v| v.to_le_bytes()));
+            content.extend((points.len() as i32).to_le_bytes());
+            content.extend(points.iter().flat_map(|(x, y): &(f64, f64)| [x.to_le_bytes(), y.to_le_bytes()]).flatten());
+
+            shp.extend((index as i32 + 1).to_be_bytes());
+            shp.extend((content.len() as i32 / 2).to_be_bytes());
+            shp.extend(content);
+        }
+        let mut header = vec![0; 100];
+        header[..4].copy_from_slice(&9994i32.to_be_bytes());
+        std::fs::write(directory.join("points.shp"), [header, shp].concat()).unwrap();
+        std::fs::copy(directory.join("polylines.dbf"), directory.join("points.dbf")).unwrap();
+
+        shapefile_split::<PointZ>(directory.join("polylines"), directory.join("points"), directory.join("segments"), "SOURCE_ID", None).unwrap();
+
+        let actual: BTreeMap<String, Vec<PointZ>> = read_polylines(
+            std::fs::File::open(directory.join("segments.shp")).unwrap(),
+            std::fs::File::open(directory.join("segments.dbf")).unwrap(),
+            "SOURCE_ID",
+        ).unwrap();
+        assert_eq!(actual["a"].iter().map(|p| (p.0, p.2)).collect::<Vec<_>>(), vec![(1.0, 1.0), (10.0, 10.0), (19.0, 19.0)]);
+        assert_eq!(actual["bb"].iter().map(|p| (p.1, p.2)).collect::<Vec<_>>(), vec![(1.0, 1.0), (9.0, 9.0)]);
+
+        let dbf = std::fs::read(directory.join("segments.dbf")).unwrap();
+        assert!(dbf.ends_with(b" a          0 bb         0\x1a"));
+        let shx = std::fs::read(directory.join("segments.shx")).unwrap();
+        assert_eq!(shx.len(), 100 + 2 * 8);
+        assert_eq!(shx[100..104], 50i32.to_be_bytes());
+
+        let points: BTreeMap<String, Vec<Point>> = read_points(
+            std::fs::File::open(directory.join("points.shp")).unwrap(),
+            std::fs::File::open(directory.join("points.dbf")).unwrap(),
+            "SOURCE_ID",
+        ).unwrap();
+        assert_eq!(points["bb"].len(), 2);
+
+        // Polylines are not points and key attribute must exist
+        let errors = [
+            read_points::<Point>(
+                std::fs::File::open(directory.join("polylines.shp")).unwrap(),
+                std::fs::File::open(directory.join("polylines.dbf")).unwrap(),
+                "SOURCE_ID",
+            ),
+            read_polylines::<Point>(
+                std::fs::File::open(directory.join("polylines.shp")).unwrap(),
+                std::fs::File::open(directory.join("polylines.dbf")).unwrap(),
+                "ID",
+            ),
+        ];
+        for error in errors {
+            assert_eq!(error.unwrap_err().kind(), &PolySplitErrorKind::InvalidFormat);
+        }
+
+        let error = shapefile_split::<Point>(directory.join("missing"), directory.join("points"), directory.join("segments"), "SOURCE_ID", None).unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::Io);
+
+        // Field length is in bytes and the header has the last update date
+        let segments = BTreeMap::from([
+            ("Zürich".to_string(), vec![vec![PointZ(0.0, 0.0, 0.0), PointZ(1.0, 0.0, 0.0)]]),
+            ("東京".to_string(), vec![vec![PointZ(0.0, 0.0, 0.0), PointZ(0.0, 1.0, 0.0)]]),
+        ]);
+        write(&directory.join("unicode"), &segments);
+        let dbf = std::fs::read(directory.join("unicode.dbf")).unwrap();
+        assert_eq!(dbf[0], 0x03);
+        assert!(dbf[1] >= 124 && (1..=12).contains(&dbf[2]) && (1..=31).contains(&dbf[3]));
+        assert_eq!(dbf.len(), 32 + 2 * 32 + 1 + 2 * (1 + 7 + 10) + 1);
+
+        let actual: BTreeMap<String, Vec<Point>> = read_polylines(
+            std::fs::File::open(directory.join("unicode.shp")).unwrap(),
+            dbf.as_slice(),
+            "SOURCE_ID",
+        ).unwrap();
+        assert_eq!(actual.keys().collect::<Vec<_>>(), vec!["Zürich", "東京"]);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        // Files written by reference tools, DBF has more records than the shapes
+        let dbf: &[u8] = include_bytes!("../tests/data/shapefile/poly.dbf");
+
+        let points: BTreeMap<String, Vec<Point>> = read_points(&include_bytes!("../tests/data/shapefile/point.shp")[..], dbf, "EAS_ID").unwrap();
+        assert_eq!(points.keys().collect::<Vec<_>>(), vec!["168"]);
+        assert_eq!(points["168"].iter().map(|p| (p.0, p.1)).collect::<Vec<_>>(), vec![(122.0, 37.0)]);
+
+        let points: BTreeMap<String, Vec<Point>> = read_points(&include_bytes!("../tests/data/shapefile/multipoint.shp")[..], dbf, "PRFEDEA").unwrap();
+        assert_eq!(points.keys().collect::<Vec<_>>(), vec!["35043411"]);
+        assert_eq!(points["35043411"].iter().map(|p| (p.0, p.1)).collect::<Vec<_>>(), vec![(122.0, 37.0), (124.0, 32.0)]);
+
+        let points: BTreeMap<String, Vec<PointZ>> = read_points(&include_bytes!("../tests/data/shapefile/pointz.shp")[..], dbf, "EAS_ID").unwrap();
+        assert_eq!(points.keys().collect::<Vec<_>>(), vec!["168", "179"]);
+        assert_eq!(points["179"][0].2, 72.58286959604922);
+
+        let multipointz: &[u8] = include_bytes!("../tests/data/shapefile/multipointz.shp");
+        let points: BTreeMap<String, Vec<PointZ>> = read_points(multipointz, dbf, "EAS_ID").unwrap();
+        assert_eq!(points["168"].iter().map(|p| p.2).collect::<Vec<_>>(), vec![
+            72.00995635986328,
+            72.0060806274414,
+            72.00220489501953,
+            71.99445343017578,
+        ]);
+
+        // Measures of -1e38 are "no data", they are not read as real ones
+        let error = read_points::<PointZM>(multipointz, dbf, "EAS_ID").unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat);
+        let error = read_points::<PointZM>(&include_bytes!("../tests/data/shapefile/pointz.shp")[..], dbf, "EAS_ID").unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat);
+
+        let error = read_polylines::<Point>(&include_bytes!("../tests/data/shapefile/line.shp")[..], dbf, "EAS_ID").unwrap_err();
+        assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat);
+        assert!(error.message.contains("must have one part"), "{}", error.message);
+    }
+
+    #[test]
+    #[cfg(feature = "wkb")]
+    fn tests_wkb() {
+        use crate::euclidean::{PointM, PointZ};
+        use crate::polysplit::PolySplitErrorKind;
+        use crate::wkb::{read_line_string, read_multi_point, read_srid, wkb_split, write_multi_line_string, ByteOrder};
+
+        enum Value {
+            U(u32),
+            F(f64),
+        }
+
+        fn wkb(byte_order: ByteOrder, values: &[Value]) -> Vec<u8> {
+            let mut wkb = vec![if byte_order == ByteOrder::BigEndian { 0 } else { 1 }];
+            for value in values {
+                match (value, byte_order) {
+                    (Value::U(value), ByteOrder::BigEndian) => wkb.extend_from_slice(&value.to_be_bytes()),
+                    (Value::U(value), ByteOrder::LittleEndian) => wkb.extend_from_slice(&value.to_le_bytes()),
+                    (Value::F(value), ByteOrder::BigEndian) => wkb.extend_from_slice(&value.to_be_bytes()),
+                    (Value::F(value), ByteOrder::LittleEndian) => wkb.extend_from_slice(&value.to_le_bytes()),
+                }
+            }
+            wkb
+        }
+
+        use Value::{F, U};
+
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            // ISO WKB
+            let line_string = wkb(byte_order, &[U(2), U(3), F(0.0), F(0.0), F(10.0), F(0.0), F(20.0), F(0.0)]);
+            let points = [
+                wkb(byte_order, &[U(4), U(2)]),
+                wkb(byte_order, &[U(1), F(1.0), F(1.0)]),
+                wkb(byte_order, &[U(1), F(19.0), F(-1.0)]),
+            ].concat();
+
+            let actual = wkb_split::<Point>(&line_string, &points, None).unwrap();
+            let expected = [
+                wkb(byte_order, &[U(5), U(1)]),
+                wkb(byte_order, &[U(2), U(3), F(1.0), F(0.0), F(10.0), F(0.0), F(19.0), F(0.0)]),
+            ].concat();
+            assert_eq!(actual, expected);
+
+            // EWKB with SRID and Z
+            let line_string = wkb(byte_order, &[U(0xA000_0002), U(4326), U(2), F(0.0), F(0.0), F(0.0), F(10.0), F(0.0), F(100.0)]);
+            let points = [
+                wkb(byte_order, &[U(0x8000_0004), U(2)]),
+                wkb(byte_order, &[U(0x8000_0001), F(5.0), F(1.0), F(0.0)]),
+                wkb(byte_order, &[U(0x8000_0001), F(11.0), F(1.0), F(0.0)]),
+            ].concat();
+
+            assert_eq!(read_srid(&line_string).unwrap(), Some(4326));
+            let actual = wkb_split::<PointZ>(&line_string, &points, None).unwrap();
+            let expected = [
+                wkb(byte_order, &[U(0xA000_0005), U(4326), U(1)]),
+                wkb(byte_order, &[U(0x8000_0002), U(2), F(5.0), F(0.0), F(50.0), F(10.0), F(0.0), F(100.0)]),
+            ].concat();
+            assert_eq!(actual, expected);
+        }
+
+        // ISO M is read and written
+        let line_string = wkb(ByteOrder::LittleEndian, &[U(2002), U(2), F(0.0), F(0.0), F(1.0), F(10.0), F(0.0), F(2.0)]);
+        let polyline: Vec<PointM> = read_line_string(&line_string).unwrap();
+        assert_eq!((polyline[0].2, polyline[1].2), (1.0, 2.0));
+        assert_eq!(read_srid(&line_string).unwrap(), None);
+
+        let actual = write_multi_line_string(&[polyline], ByteOrder::LittleEndian, None);
+        let expected = [
+            wkb(ByteOrder::LittleEndian, &[U(2005), U(1)]),
+            wkb(ByteOrder::LittleEndian, &[U(2002), U(2), F(0.0), F(0.0), F(1.0), F(10.0), F(0.0), F(2.0)]),
+        ].concat();
+        assert_eq!(actual, expected);
+
+        let errors = [
+            read_line_string::<Point>(&wkb(ByteOrder::LittleEndian, &[U(1), F(0.0), F(0.0)])),
+            read_line_string::<Point>(&wkb(ByteOrder::LittleEndian, &[U(2), U(2), F(0.0), F(0.0)])),
+            read_line_string::<Point>(&[wkb(ByteOrder::LittleEndian, &[U(2), U(0)]), vec![0]].concat()),
+            read_line_string::<Point>(&[2, 2, 0, 0, 0, 0, 0, 0, 0]),
+            read_multi_point::<Point>(&[
+                wkb(ByteOrder::LittleEndian, &[U(4), U(1)]),
+                wkb(ByteOrder::LittleEndian, &[U(1001), F(0.0), F(0.0), F(0.0)]),
+            ].concat()),
+        ];
+
+        for error in errors {
+            assert_eq!(error.unwrap_err().kind(), &PolySplitErrorKind::InvalidFormat);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "wkt")]
+    fn tests_wkt() {
+        use crate::euclidean::{PointM, PointZM};
+        use crate::polysplit::PolySplitErrorKind;
+        use crate::wkt::{read_line_string, read_multi_point, wkt_split};
+
+        let tests = [
+            (
+                "LINESTRING (0 0, 10 0, 20 0)",
+                "MULTIPOINT ((1 1), (10 -1), (19 1))",
+                "MULTILINESTRING ((1 0, 10 0), (10 0, 19 0))",
+            ),
+
+            (
+                "SRID=4326;linestring(0 0,10 0,20 0)",
+                "multipoint(1 1,19 1)",
+                "MULTILINESTRING ((1 0, 10 0, 19 0))",
+            ),
+
+            (
+                "LINESTRING (0 0, 1e1 0, 2.0E1 0)",
+                "MULTIPOINT (1.5 1, 19.25 -1)",
+                "MULTILINESTRING ((1.5 0, 10 0, 19.25 0))",
+            ),
+        ];
+
+        for (line_string, points, expected) in &tests {
+            assert_eq!(wkt_split::<Point>(line_string, points, None).unwrap(), *expected);
+        }
+
+        // Ordinates are round-tripped and interpolated
+        let actual = wkt_split::<PointZM>(
+            "LINESTRING ZM (0 0 0 0, 10 0 100 1000)",
+            "MULTIPOINT ZM ((1 1 0 0), (9 1 0 0))",
+            None,
+        ).unwrap();
+        assert_eq!(actual, "MULTILINESTRING ZM ((1 0 10 100, 9 0 90 900))");
+
+        let actual = wkt_split::<PointM>("LINESTRING M (0 0 0, 10 0 10)", "MULTIPOINT M (5 1 0, 10 1 0)", None).unwrap();
+        assert_eq!(actual, "MULTILINESTRING M ((5 0 5, 10 0 10))");
+
+        let points: Vec<PointZM> = read_line_string("LINESTRING Z (0 0 1, 10 0 2)").unwrap();
+        assert_eq!((points[1].2, points[1].3), (2.0, 0.0));
+
+        let points: Vec<Point> = read_multi_point("MULTIPOINT EMPTY").unwrap();
+        assert!(points.is_empty());
+
+        for wkt in ["POINT (0 0)", "LINESTRING (0 0, 1)", "LINESTRING M (0 0, 1 1)", "LINESTRING (0 0, 1 1", "LINESTRING (0 0) x"] {
+            let error = read_line_string::<Point>(wkt).unwrap_err();
+            assert_eq!(error.kind(), &PolySplitErrorKind::InvalidFormat, "{}", wkt);
+        }
+    }
+
     #[test]
     fn tests_polyline_errors() {
         let points : Vec<Point> = vec![];
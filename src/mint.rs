@@ -0,0 +1,66 @@
+//! [PolySplit](crate::PolySplit) and [Interpolate](crate::Interpolate) implementations
+//! for [mint](https://docs.rs/mint) types enabled by `mint` feature,
+//! so any math library supporting `mint` can be bridged.
+//!
+//! `Point2` and `Vector2` are planar and `Point3` and `Vector3` are spatial euclidean points.
+use mint::{Point2, Point3, Vector2, Vector3};
+
+use crate::euclidean::{impl_euclidean_polysplit, lerp, Coordinate, Euclidean};
+
+impl<T: Coordinate> Euclidean<T> for Point2<T> {
+    fn position(&self) -> [T; 3] {
+        [self.x, self.y, T::zero()]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Point2 { x: lerp(self.x, to.x, ratio), y: lerp(self.y, to.y, ratio) }
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for Vector2<T> {
+    fn position(&self) -> [T; 3] {
+        [self.x, self.y, T::zero()]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Vector2 { x: lerp(self.x, to.x, ratio), y: lerp(self.y, to.y, ratio) }
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for Point3<T> {
+    fn position(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Point3 { x: lerp(self.x, to.x, ratio), y: lerp(self.y, to.y, ratio), z: lerp(self.z, to.z, ratio) }
+    }
+}
+
+impl<T: Coordinate> Euclidean<T> for Vector3<T> {
+    fn position(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Vector3 { x: lerp(self.x, to.x, ratio), y: lerp(self.y, to.y, ratio), z: lerp(self.z, to.z, ratio) }
+    }
+}
+
+impl_euclidean_polysplit!(Point2<T>, Vector2<T>, Point3<T>, Vector3<T>);
@@ -0,0 +1,37 @@
+//! [PolySplit](crate::PolySplit) and [Interpolate](crate::Interpolate) implementations
+//! for [nalgebra](https://docs.rs/nalgebra) points enabled by `nalgebra` feature.
+//!
+//! `Point2` is planar and `Point3` is spatial euclidean point.
+use nalgebra::{Point2, Point3, Scalar};
+
+use crate::euclidean::{impl_euclidean_polysplit, lerp, Coordinate, Euclidean};
+
+impl<T: Coordinate + Scalar> Euclidean<T> for Point2<T> {
+    fn position(&self) -> [T; 3] {
+        [self.x, self.y, T::zero()]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Point2::new(lerp(self.x, to.x, ratio), lerp(self.y, to.y, ratio))
+    }
+}
+
+impl<T: Coordinate + Scalar> Euclidean<T> for Point3<T> {
+    fn position(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    fn interpolate(&self, to: &Self, ratio: T) -> Self {
+        Point3::new(lerp(self.x, to.x, ratio), lerp(self.y, to.y, ratio), lerp(self.z, to.z, ratio))
+    }
+}
+
+impl_euclidean_polysplit!(impl [T: Coordinate + Scalar] T => Point2<T>, Point3<T>);
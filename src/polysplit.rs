@@ -1,11 +1,38 @@
-use std::fmt;
-use std::error;
-use std::cmp::{Ord, Ordering, PartialEq, Eq, PartialOrd};
-use std::collections::BinaryHeap;
-use std::fmt::Debug;
-use std::ops::Add;
+use alloc::collections::BinaryHeap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::cmp::{Ord, Ordering, PartialEq, Eq, PartialOrd};
+use core::fmt::Debug;
+use core::ops::Add;
+
+/// Emits `tracing` event if `tracing` feature is enabled, otherwise expands to nothing.
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::event!($($arg)*);
+    };
+}
+
+/// Enters `tracing` span till the end of the current scope if `tracing` feature is enabled,
+/// otherwise expands to nothing.
+macro_rules! trace_span {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!($($arg)*).entered();
+    };
+}
 
 /// CutRatioResult presents the closest projection of the point to the segment.
+///
+/// Results are ordered `Begin < Medium < Fraction < End`, ratios of the same variant are
+/// compared by value. `Medium` and `Fraction` are not expected to mix, [PolySplit]
+/// implementations produce one of them, so they are ordered by variant rather than by
+/// a rounded value.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
 pub enum CutRatioResult {
     /// The closest projection is the start of the segment.
     Begin,
@@ -13,18 +40,27 @@ pub enum CutRatioResult {
     /// the defined proportion (usually `0.0 < ratio < 1.0` where `0.0` is the start
     /// and `1.0` is the end of the segment).
     Medium(f64),
+    /// The closest projection is the point on the segment that splits it in
+    /// the exact [Fraction], it is compared by cross-multiplication without rounding.
+    Fraction(Fraction),
     /// The closest projection is the end of the segment.
     End,
 }
 
+impl CutRatioResult {
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Begin => 0,
+            Self::Medium(_) => 1,
+            Self::Fraction(_) => 2,
+            Self::End => 3,
+        }
+    }
+}
+
 impl PartialEq for CutRatioResult {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Self::Begin, Self::Begin) => true,
-            (Self::Medium(s), Self::Medium(o)) => s.eq(o),
-            (Self::End, Self::End) => true,
-            _ => false,
-        }
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -39,18 +75,123 @@ impl PartialOrd for CutRatioResult {
 impl Ord for CutRatioResult {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
-            (Self::Begin, Self::Begin) => Ordering::Equal,
-            (Self::Begin, _) => Ordering::Less,
-            (Self::Medium(_), Self::Begin) => Ordering::Greater,
             (Self::Medium(s), Self::Medium(o)) => s.total_cmp(o),
-            (Self::Medium(_), Self::End) => Ordering::Less,
-            (Self::End, Self::End) => Ordering::Equal,
-            (Self::End, _) => Ordering::Greater,
+            (Self::Fraction(s), Self::Fraction(o)) => s.cmp(o),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+/// Exact ratio `numerator / denominator` with positive denominator.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::Fraction;
+///
+/// let half = Fraction::new(1, 2).unwrap();
+/// assert_eq!(Fraction::new(-2, -4), Some(half));
+/// assert_eq!(Fraction::new(-1, 2).unwrap().numerator(), -1);
+/// assert!(Fraction::new(1, 0).is_none());
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "(i128, i128)", into = "(i128, i128)"))]
+pub struct Fraction {
+    pub(super) numerator: i128,
+    pub(super) denominator: i128,
+}
+
+impl Fraction {
+    /// Creates fraction moving the sign of the denominator to the numerator,
+    /// `None` if the denominator is zero or the sign cannot be moved.
+    pub fn new(numerator: i128, denominator: i128) -> Option<Self> {
+        match denominator.cmp(&0) {
+            Ordering::Greater => Some(Fraction { numerator, denominator }),
+            Ordering::Less => Some(Fraction { numerator: numerator.checked_neg()?, denominator: denominator.checked_neg()? }),
+            Ordering::Equal => None,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+}
+
+impl PartialEq for Fraction {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Fraction {}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Fractions are compared exactly, cross products are computed with 256 bits.
+impl Ord for Fraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let sign = |n: i128| n.cmp(&0);
+
+        match sign(self.numerator).cmp(&sign(other.numerator)) {
+            Ordering::Equal => {}
+            v => return v,
+        }
+
+        let left = widening_mul(self.numerator.unsigned_abs(), other.denominator.unsigned_abs());
+        let right = widening_mul(other.numerator.unsigned_abs(), self.denominator.unsigned_abs());
+
+        if self.numerator < 0 {
+            right.cmp(&left)
+        } else {
+            left.cmp(&right)
         }
     }
 }
 
+impl TryFrom<(i128, i128)> for Fraction {
+    type Error = &'static str;
+
+    fn try_from((numerator, denominator): (i128, i128)) -> core::result::Result<Self, Self::Error> {
+        Fraction::new(numerator, denominator).ok_or("fraction denominator must be non-zero")
+    }
+}
+
+impl From<Fraction> for (i128, i128) {
+    fn from(fraction: Fraction) -> Self {
+        (fraction.numerator, fraction.denominator)
+    }
+}
+
+/// Returns the full 256-bit product of two values as `(high, low)` parts.
+pub(crate) fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let middle = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let lo = (middle << 64) | (lo_lo & MASK);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64);
+
+    (hi, lo)
+}
+
 /// DistanceToSegmentResult presents the projection results of the point to the segment.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DistanceToSegmentResult<P, D>
 where
     P: Copy,
@@ -61,6 +202,26 @@ where
     pub distance: D,
 }
 
+impl<P, D> DistanceToSegmentResult<P, D>
+where
+    P: Copy,
+    D: Copy + PartialOrd + Add<Output = D>,
+{
+    /// Converts the cut point to another point type keeping other results,
+    /// it is handy to implement [PolySplit] by delegating to already existing implementation.
+    pub fn map_cut_point<Q, F>(self, f: F) -> DistanceToSegmentResult<Q, D>
+    where
+        Q: Copy,
+        F: FnOnce(P) -> Q,
+    {
+        DistanceToSegmentResult{
+            cut_ratio: self.cut_ratio,
+            cut_point: f(self.cut_point),
+            distance: self.distance,
+        }
+    }
+}
+
 /// PolySplit defines methods for types that can be used in **polyline_split** method.
 pub trait PolySplit<D>
 where
@@ -79,18 +240,114 @@ where
     ///
     /// * `segment` - A segment presented by a tuple of points
     fn distance_to_segment(&self, segment: (&Self, &Self)) -> DistanceToSegmentResult<Self, D>;
+    /// Returns projection [results](DistanceToSegmentResult) to the segment,
+    /// segments not longer than `tolerance` are projected to their start
+    /// instead of the precision the type uses by default.
+    ///
+    /// The default implementation ignores `tolerance`.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment` - A segment presented by a tuple of points
+    /// * `tolerance` - A length segments are treated as degenerate within
+    fn distance_to_segment_with_tolerance(
+        &self,
+        segment: (&Self, &Self),
+        tolerance: D,
+    ) -> DistanceToSegmentResult<Self, D> {
+        let _ = tolerance;
+        self.distance_to_segment(segment)
+    }
+    /// Returns `false` if the point cannot be used for splitting,
+    /// for example when it has NaN or infinite coordinates.
+    fn is_valid(&self) -> bool {
+        true
+    }
+    /// Returns the sum of two distances or `None` if it cannot be presented by the distance type,
+    /// types with bounded distances like integers should use checked arithmetic.
+    fn add_distances(a: D, b: D) -> Option<D> {
+        Some(a + b)
+    }
+}
+
+/// Interpolate defines methods for types that can create points along segments,
+/// for example to resample polylines or to extract their parts.
+pub trait Interpolate<D>: PolySplit<D>
+where
+    D: Copy + PartialOrd + Add<Output = D>,
+{
+    /// Returns length of the segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment` - A segment presented by a tuple of points
+    fn segment_length(segment: (&Self, &Self)) -> D {
+        segment.0.distance_to_point(segment.1)
+    }
+    /// Returns the point splitting the segment in the defined proportion,
+    /// where `0.0` is the start and `1.0` is the end of the segment.
+    /// Ratios out of this range extrapolate the segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment` - A segment presented by a tuple of points
+    /// * `ratio` - A proportion the segment should be split in
+    fn interpolate(segment: (&Self, &Self), ratio: f64) -> Self;
+    /// Returns the point at the defined distance from the start of the segment.
+    /// Distances out of the segment length extrapolate the segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment` - A segment presented by a tuple of points
+    /// * `distance` - A distance from the start of the segment
+    fn interpolate_at_distance(segment: (&Self, &Self), distance: D) -> Self;
+    /// Returns the point at the defined distance from the start of the segment,
+    /// segments not longer than `tolerance` return their start.
+    ///
+    /// The default implementation ignores `tolerance`.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment` - A segment presented by a tuple of points
+    /// * `distance` - A distance from the start of the segment
+    /// * `tolerance` - A length segments are treated as degenerate within
+    fn interpolate_at_distance_with_tolerance(segment: (&Self, &Self), distance: D, tolerance: D) -> Self {
+        let _ = tolerance;
+        Self::interpolate_at_distance(segment, distance)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[non_exhaustive]
 pub enum PolySplitErrorKind {
     InvalidPolyline,
     InvalidPoints,
+    InvalidCoordinate(InputIndex),
     PointFarAway,
     CannotSplit,
+    /// Input geometry cannot be read from or written to the format.
+    InvalidFormat,
+    /// Reading or writing failed with I/O error.
+    Io,
+    /// Metric parameters are not valid, e.g. non-positive anisotropic weights.
+    InvalidMetric,
+}
+
+/// InputIndex presents the position of the input point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum InputIndex {
+    /// Index of the polyline point.
+    Polyline(usize),
+    /// Index of the point the polyline is split by.
+    Points(usize),
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PolySplitError {
     pub(super) kind: PolySplitErrorKind,
     pub message: String,
@@ -102,18 +359,60 @@ impl PolySplitError {
     }
 }
 
+/// Returns [PolySplitErrorKind::InvalidFormat] error, it is used by optional format modules.
+#[cfg(any(
+    feature = "csv",
+    feature = "encoded-polyline",
+    feature = "flatgeobuf",
+    feature = "geoarrow",
+    feature = "geojson",
+    feature = "geoparquet",
+    feature = "gpx",
+    feature = "kml",
+    feature = "shapefile",
+    feature = "wkb",
+    feature = "wkt",
+))]
+pub(crate) fn invalid_format(message: &str) -> PolySplitError {
+    PolySplitError{
+        kind: PolySplitErrorKind::InvalidFormat,
+        message: message.to_string(),
+    }
+}
+
+/// Prefixes the error message with the id of the polyline it is related to,
+/// it is used by optional format modules splitting several polylines.
+#[cfg(any(feature = "csv", feature = "flatgeobuf", feature = "shapefile"))]
+pub(crate) fn with_polyline_id(error: PolySplitError, id: &str) -> PolySplitError {
+    PolySplitError{
+        kind: error.kind,
+        message: format!("polyline {}: {}", id, error.message),
+    }
+}
+
 impl fmt::Display for PolySplitError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.message)
     }
 }
 
-impl error::Error for PolySplitError {}
+#[cfg(feature = "std")]
+impl std::error::Error for PolySplitError {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for PolySplitError {
+    fn from(error: std::io::Error) -> Self {
+        PolySplitError{
+            kind: PolySplitErrorKind::Io,
+            message: error.to_string(),
+        }
+    }
+}
 
-pub type Result<T> = std::result::Result<T, PolySplitError>;
+pub type Result<T> = core::result::Result<T, PolySplitError>;
 
 struct CutPoint<P>
-where P: std::fmt::Debug {
+where P: Debug {
     segment_index: usize,
     cut_ratio: CutRatioResult,
     cut_point: P,
@@ -125,6 +424,23 @@ struct Vertex<D> {
     distance_to: D,
 }
 
+/// Points projected to the segment, either candidates listed by their indexes or all of them.
+enum SegmentPoints<'a, P> {
+    Candidates(&'a [P], core::slice::Iter<'a, usize>),
+    All(core::slice::Iter<'a, P>),
+}
+
+impl<'a, P> Iterator for SegmentPoints<'a, P> {
+    type Item = &'a P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Candidates(points, indexes) => indexes.next().map(|&point_index| &points[point_index]),
+            Self::All(points) => points.next(),
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq)]
 struct State<D> {
     distance_total: D,
@@ -142,7 +458,7 @@ impl<D: Copy + PartialOrd> Ord for State<D> {
         // to make implementations of `PartialEq` and `Ord` consistent.
         other.distance_total
             .partial_cmp(&self.distance_total)
-            .unwrap()
+            .unwrap_or(Ordering::Equal)
             .then_with(|| self.position.cmp(&other.position))
     }
 }
@@ -154,6 +470,43 @@ impl<D: Copy + PartialOrd> PartialOrd for State<D> {
     }
 }
 
+/// Snaps projection to the segment start or end if it is within `tolerance`.
+fn snap_to_vertex<P, D>(
+    psd: &mut DistanceToSegmentResult<P, D>,
+    point: &P,
+    segment: (&P, &P),
+    tolerance: D,
+    is_degenerate: bool,
+)
+where
+    P: PolySplit<D>,
+    D: Copy + PartialOrd + Add<Output = D>,
+{
+    if is_degenerate {
+        if !matches!(psd.cut_ratio, CutRatioResult::Begin) {
+            psd.cut_ratio = CutRatioResult::Begin;
+            psd.cut_point = *segment.0;
+            psd.distance = point.distance_to_point(segment.0);
+        }
+
+        return;
+    }
+
+    if matches!(psd.cut_ratio, CutRatioResult::Begin | CutRatioResult::End) {
+        return;
+    }
+
+    if psd.cut_point.distance_to_point(segment.0) <= tolerance {
+        psd.cut_ratio = CutRatioResult::Begin;
+        psd.cut_point = *segment.0;
+        psd.distance = point.distance_to_point(segment.0);
+    } else if psd.cut_point.distance_to_point(segment.1) <= tolerance {
+        psd.cut_ratio = CutRatioResult::End;
+        psd.cut_point = *segment.1;
+        psd.distance = point.distance_to_point(segment.1);
+    }
+}
+
 /// Splits polyline into segments by the defined list of points.
 ///
 /// # Examples
@@ -183,7 +536,67 @@ pub fn polyline_split<P, D>(
     distance_threshold: Option<D>,
 ) -> Result<Vec<Vec<P>>>
 where
-    P: PolySplit<D> + std::fmt::Debug,
+    P: PolySplit<D> + Debug,
+    D: Copy + PartialOrd + Add<Output = D>,
+{
+    split(polyline, points, distance_threshold, None, None)
+}
+
+/// Splits polyline into segments by the defined list of points
+/// using geometric tolerance.
+///
+/// Polyline segments not longer than `tolerance` are treated as degenerate,
+/// and projections closer than `tolerance` to the segment start or end are snapped to it,
+/// so near-vertex projections do not create sliver segments.
+/// Projections use [PolySplit::distance_to_segment_with_tolerance], so the tolerance
+/// replaces the default tolerance of the point type,
+/// e.g. [Coordinate::DEGENERATE_TOLERANCE](crate::euclidean::Coordinate::DEGENERATE_TOLERANCE).
+/// The tolerance is expressed in the same units as distances of the point type.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::euclidean::Point;
+/// use polysplit::polyline_split_with_tolerance;
+///
+/// let polyline = vec![
+///     Point(0.0, 0.0),
+///     Point(10.0, 0.0),
+///     Point(20.0, 0.0),
+/// ];
+/// let points = vec![
+///     Point(9.9999, 1.0),
+///     Point(19.0, 1.0),
+/// ];
+///
+/// let segments = polyline_split_with_tolerance(&polyline, &points, None, 0.001).unwrap();
+///
+/// assert_eq!(segments[0][0].0, 10.0);
+/// ```
+pub fn polyline_split_with_tolerance<P, D>(
+    polyline: &[P],
+    points: &[P],
+    distance_threshold: Option<D>,
+    tolerance: D,
+) -> Result<Vec<Vec<P>>>
+where
+    P: PolySplit<D> + Debug,
+    D: Copy + PartialOrd + Add<Output = D>,
+{
+    split(polyline, points, distance_threshold, Some(tolerance), None)
+}
+
+/// Splits polyline, if `candidates` are defined only points with listed indexes
+/// are projected to the segment with the same index as the list.
+pub(crate) fn split<P, D>(
+    polyline: &[P],
+    points: &[P],
+    distance_threshold: Option<D>,
+    tolerance: Option<D>,
+    candidates: Option<&[Vec<usize>]>,
+) -> Result<Vec<Vec<P>>>
+where
+    P: PolySplit<D> + Debug,
     D: Copy + PartialOrd + Add<Output = D>,
 {
     if polyline.len() <= 1 {
@@ -200,173 +613,259 @@ where
         });
     }
 
-    let segments_len = polyline.len() - 1;
-    let points_len = points.len();
+    if let Some(index) = polyline.iter().position(|p| !p.is_valid()) {
+        return Err(PolySplitError{
+            kind: PolySplitErrorKind::InvalidCoordinate(InputIndex::Polyline(index)),
+            message: format!("polyline point {} has invalid coordinate", index),
+        });
+    }
 
-    // Collecting all possible cut points
-    let mut cut_points: Vec<CutPoint<P>> = Vec::new();
+    if let Some(index) = points.iter().position(|p| !p.is_valid()) {
+        return Err(PolySplitError{
+            kind: PolySplitErrorKind::InvalidCoordinate(InputIndex::Points(index)),
+            message: format!("point {} has invalid coordinate", index),
+        });
+    }
 
-    for segment_index in 0..segments_len {
-        let segment_a = &polyline[segment_index];
-        let segment_b = &polyline[segment_index + 1];
+    let segments_len = polyline.len() - 1;
+    let points_len = points.len();
 
-        let mut is_start_added = false;
-        let mut is_end_added = false;
+    trace_span!(tracing::Level::DEBUG, "polyline_split", segments_len, points_len);
 
-        for point in points.iter() {
-            let psd: DistanceToSegmentResult<P, D> = point.distance_to_segment((segment_a, segment_b));
-            if let Some(dt) = distance_threshold {
-                if psd.distance > dt {
-                    continue;
+    // Collecting all possible cut points
+    let cut_points = {
+        trace_span!(tracing::Level::DEBUG, "candidate_generation");
+
+        let mut cut_points: Vec<CutPoint<P>> = Vec::new();
+
+        for segment_index in 0..segments_len {
+            let segment_a = &polyline[segment_index];
+            let segment_b = &polyline[segment_index + 1];
+
+            let mut is_start_added = false;
+            let mut is_end_added = false;
+
+            let is_degenerate = tolerance.is_some_and(|t| segment_a.distance_to_point(segment_b) <= t);
+
+            let segment_points = match candidates {
+                Some(candidates) => SegmentPoints::Candidates(
+                    points,
+                    candidates.get(segment_index).map(Vec::as_slice).unwrap_or_default().iter(),
+                ),
+                None => SegmentPoints::All(points.iter()),
+            };
+
+            for point in segment_points {
+                let psd: DistanceToSegmentResult<P, D> = match tolerance {
+                    Some(t) => {
+                        let mut psd = point.distance_to_segment_with_tolerance((segment_a, segment_b), t);
+                        snap_to_vertex(&mut psd, point, (segment_a, segment_b), t, is_degenerate);
+                        psd
+                    },
+                    None => point.distance_to_segment((segment_a, segment_b)),
+                };
+
+                if let Some(dt) = distance_threshold {
+                    if psd.distance > dt {
+                        continue;
+                    }
                 }
-            }
 
-            match psd.cut_ratio {
-                CutRatioResult::Begin => {
-                    if segment_index == 0 && !is_start_added {
-                        cut_points.push(CutPoint {
-                            segment_index,
-                            cut_ratio: psd.cut_ratio,
-                            cut_point: *segment_a,
-                        });
-
-                        is_start_added = true;
+                match psd.cut_ratio {
+                    CutRatioResult::Begin => {
+                        if segment_index == 0 && !is_start_added {
+                            cut_points.push(CutPoint {
+                                segment_index,
+                                cut_ratio: psd.cut_ratio,
+                                cut_point: *segment_a,
+                            });
+
+                            is_start_added = true;
+                        }
                     }
-                }
 
-                CutRatioResult::End => {
-                    if !is_end_added {
+                    CutRatioResult::End => {
+                        if !is_end_added {
+                            cut_points.push(CutPoint {
+                                segment_index,
+                                cut_ratio: psd.cut_ratio,
+                                cut_point: *segment_b,
+                            });
+
+                            is_end_added = true;
+                        }
+                    },
+
+                    _ => {
                         cut_points.push(CutPoint {
                             segment_index,
                             cut_ratio: psd.cut_ratio,
-                            cut_point: *segment_b,
+                            cut_point: psd.cut_point,
                         });
-
-                        is_end_added = true;
                     }
-                },
-
-                _ => {
-                    cut_points.push(CutPoint {
-                        segment_index,
-                        cut_ratio: psd.cut_ratio,
-                        cut_point: psd.cut_point,
-                    });
                 }
             }
         }
-    }
 
-    cut_points.sort_unstable_by(|a, b| {
-        match a.segment_index.cmp(&b.segment_index) {
-            Ordering::Equal => a.cut_ratio.partial_cmp(&b.cut_ratio).unwrap(),
-            v => v,
-        }
-    });
+        cut_points.sort_unstable_by(|a, b| {
+            match a.segment_index.cmp(&b.segment_index) {
+                Ordering::Equal => a.cut_ratio.cmp(&b.cut_ratio),
+                v => v,
+            }
+        });
+
+        trace_event!(tracing::Level::DEBUG, cut_points = cut_points.len(), "cut points collected");
+
+        cut_points
+    };
 
     // Building graph
-    let mut vertexes: Vec<Vertex<D>> = Vec::new();
-    let mut edges: Vec<(usize, usize)> = Vec::new();
+    let (vertexes, edges) = {
+        trace_span!(tracing::Level::DEBUG, "graph_building");
 
-    let mut last_reachable_cut_point_index = 0;
+        let mut vertexes: Vec<Vertex<D>> = Vec::new();
+        let mut edges: Vec<(usize, usize)> = Vec::new();
 
-    for (point_index, point) in points.iter().enumerate() {
-        let start_position = vertexes.len();
-        let mut first_match_cut_point_index = None;
+        let mut last_reachable_cut_point_index = 0;
 
-        for (cut_point_index, cut_point) in cut_points.iter().enumerate().skip(last_reachable_cut_point_index) {
-            let distance_to = point.distance_to_point(&cut_point.cut_point);
-            if let Some(dt) = distance_threshold {
-                if distance_to > dt {
-                    continue;
+        for (point_index, point) in points.iter().enumerate() {
+            let start_position = vertexes.len();
+            let mut first_match_cut_point_index = None;
+
+            for (cut_point_index, cut_point) in cut_points.iter().enumerate().skip(last_reachable_cut_point_index) {
+                let distance_to = point.distance_to_point(&cut_point.cut_point);
+                if let Some(dt) = distance_threshold {
+                    if distance_to > dt {
+                        continue;
+                    }
+                }
+
+                if first_match_cut_point_index.is_none() {
+                    first_match_cut_point_index = Some(cut_point_index);
                 }
+
+                vertexes.push(Vertex {
+                    point_index,
+                    cut_point_index,
+                    distance_to,
+                });
             }
 
-            if first_match_cut_point_index.is_none() {
-                first_match_cut_point_index = Some(cut_point_index);
+            let end_position = vertexes.len();
+            if start_position == end_position {
+                return Err(PolySplitError{
+                    kind: PolySplitErrorKind::PointFarAway,
+                    message: "point has no closest segments".to_string(),
+                });
             }
 
-            vertexes.push(Vertex {
+            edges.push((start_position, end_position));
+            last_reachable_cut_point_index = first_match_cut_point_index.unwrap_or_default();
+
+            trace_event!(
+                tracing::Level::TRACE,
                 point_index,
-                cut_point_index,
-                distance_to,
-            });
+                vertexes = end_position - start_position,
+                last_reachable_cut_point_index,
+                "point vertexes added",
+            );
         }
 
-        let end_position = vertexes.len();
-        if start_position == end_position {
-            return Err(PolySplitError{
-                kind: PolySplitErrorKind::PointFarAway,
-                message: "point has no closest segments".to_string(),
+        trace_event!(tracing::Level::DEBUG, vertexes = vertexes.len(), "graph built");
+
+        (vertexes, edges)
+    };
+
+    // Searching for shortest path using Dijkstra's algorithm
+    let mut path = {
+        trace_span!(tracing::Level::DEBUG, "shortest_path_search");
+
+        // Initializing start points
+        let vertexes_len = vertexes.len();
+        let mut dist: Vec<Option<D>> = (0..vertexes_len).map(|_| None).collect();
+        let mut prev: Vec<Option<usize>> = (0..vertexes_len).map(|_| None).collect();
+        let mut priority_queue = BinaryHeap::new();
+
+        for idx in edges[0].0..edges[0].1 {
+            let vertex = &vertexes[idx];
+
+            dist[idx] = Some(vertex.distance_to);
+            prev[idx] = None;
+            priority_queue.push(State {
+                distance_total: vertex.distance_to,
+                position: idx,
             });
         }
 
-        edges.push((start_position, end_position));
-        last_reachable_cut_point_index = first_match_cut_point_index.unwrap_or_default();
-    }
-
-    // Initializing start points
-    let vertexes_len = vertexes.len();
-    let mut dist: Vec<Option<D>> = (0..vertexes_len).map(|_| None).collect();
-    let mut prev: Vec<Option<usize>> = (0..vertexes_len).map(|_| None).collect();
-    let mut priority_queue = BinaryHeap::new();
+        #[cfg(feature = "tracing")]
+        let (mut pops, mut relaxations) = (0usize, 0usize);
 
-    for idx in edges[0].0..edges[0].1 {
-        let vertex = &vertexes[idx];
+        let mut destination = None;
+        while let Some(State { distance_total, position }) = priority_queue.pop() {
+            let current_vertex = &vertexes[position];
 
-        dist[idx] = Some(vertex.distance_to);
-        prev[idx] = None;
-        priority_queue.push(State {
-            distance_total: vertex.distance_to,
-            position: idx,
-        });
-    }
+            #[cfg(feature = "tracing")]
+            {
+                pops += 1;
+            }
+            trace_event!(tracing::Level::TRACE, position, point_index = current_vertex.point_index, "vertex popped");
 
-    // Searching for shortest path using Dijkstra's algorithm
-    let mut destination = None;
-    while let Some(State { distance_total, position }) = priority_queue.pop() {
-        let current_vertex = &vertexes[position];
-
-        // Goal is reached
-        if current_vertex.point_index + 1 == points_len {
-            destination = Some(position);
-            break;
-        }
+            // Goal is reached
+            if current_vertex.point_index + 1 == points_len {
+                destination = Some(position);
+                break;
+            }
 
-        // Useless state because there is better one
-        if let Some(d) = dist[position] {
-            if distance_total > d {
-                continue;
+            // Useless state because there is better one
+            if let Some(d) = dist[position] {
+                if distance_total > d {
+                    continue;
+                }
             }
-        }
 
-        // Iterating connected vertexes
-        let (from_idx, to_idx) = edges[current_vertex.point_index + 1];
-        for idx in from_idx..to_idx {
-            let neighbour_vertex = &vertexes[idx];
+            // Iterating connected vertexes
+            let (from_idx, to_idx) = edges[current_vertex.point_index + 1];
+            for idx in from_idx..to_idx {
+                let neighbour_vertex = &vertexes[idx];
 
-            if current_vertex.cut_point_index > neighbour_vertex.cut_point_index {
-                continue;
-            }
+                if current_vertex.cut_point_index > neighbour_vertex.cut_point_index {
+                    continue;
+                }
 
-            let relaxed_distance_total = distance_total + neighbour_vertex.distance_to;
-            if dist[idx].map_or(true, |d| d > relaxed_distance_total) {
-                dist[idx] = Some(relaxed_distance_total);
-                prev[idx] = Some(position);
-                priority_queue.push(State {
-                    distance_total: relaxed_distance_total,
-                    position: idx,
-                });
+                let Some(relaxed_distance_total) = P::add_distances(distance_total, neighbour_vertex.distance_to) else {
+                    return Err(PolySplitError{
+                        kind: PolySplitErrorKind::CannotSplit,
+                        message: "total distance overflows".to_string(),
+                    });
+                };
+                if dist[idx].is_none_or(|d| d > relaxed_distance_total) {
+                    #[cfg(feature = "tracing")]
+                    {
+                        relaxations += 1;
+                    }
+                    trace_event!(tracing::Level::TRACE, from = position, to = idx, "edge relaxed");
+
+                    dist[idx] = Some(relaxed_distance_total);
+                    prev[idx] = Some(position);
+                    priority_queue.push(State {
+                        distance_total: relaxed_distance_total,
+                        position: idx,
+                    });
+                }
             }
         }
-    }
 
-    // Restoring path
-    let mut path = Vec::new();
-    while let Some(idx) = destination {
-        path.push(idx);
-        destination = prev[idx];
-    }
+        trace_event!(tracing::Level::DEBUG, pops, relaxations, found = destination.is_some(), "shortest path searched");
+
+        // Restoring path
+        let mut path = Vec::new();
+        while let Some(idx) = destination {
+            path.push(idx);
+            destination = prev[idx];
+        }
+
+        path
+    };
 
     if path.is_empty() {
         return Err(PolySplitError{
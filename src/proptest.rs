@@ -0,0 +1,130 @@
+//! [proptest](https://docs.rs/proptest) strategies enabled by `proptest` feature.
+//!
+//! Strategies generate polylines and ordered points near them, so own [PolySplit](crate::PolySplit)
+//! implementations can be property-tested against the crate invariants.
+use std::ops::Range;
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::euclidean::Point;
+
+/// Polyline, points and the expected result of splitting the polyline by these points.
+#[derive(Clone, Debug)]
+pub struct KnownSplit {
+    pub polyline: Vec<Point>,
+    pub points: Vec<Point>,
+    pub segments: Vec<Vec<Point>>,
+}
+
+/// Returns strategy generating points with coordinates within `bounds`.
+pub fn point(bounds: Range<f64>) -> impl Strategy<Value = Point> {
+    (bounds.clone(), bounds).prop_map(|(x, y)| Point(x, y))
+}
+
+/// Returns strategy generating polylines with coordinates within `bounds`
+/// and the number of points within `len`.
+pub fn polyline(bounds: Range<f64>, len: Range<usize>) -> impl Strategy<Value = Vec<Point>> {
+    vec(point(bounds), len)
+}
+
+/// Returns strategy generating points ordered along `polyline`,
+/// each point is shifted from its position on the polyline perpendicularly
+/// to the segment by at most `noise`, so the position is the projection of the point
+/// to the segment and every point has a cut point within `noise`.
+pub fn noisy_points(polyline: Vec<Point>, len: Range<usize>, noise: f64) -> impl Strategy<Value = Vec<Point>> {
+    let segments_len = polyline.len().saturating_sub(1).max(1);
+    let noise = noise.abs();
+
+    vec((0..segments_len, 0.0..=1.0f64, -1.0..=1.0f64), len).prop_map(move |mut positions| {
+        positions.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        positions
+            .into_iter()
+            .map(|(segment_index, ratio, shift)| {
+                let p = position(&polyline, segment_index, ratio);
+                let (nx, ny) = normal(&polyline, segment_index);
+                Point(p.0 + noise * shift * nx, p.1 + noise * shift * ny)
+            })
+            .collect()
+    })
+}
+
+/// Returns strategy generating splits with known result.
+///
+/// Polylines are monotone along x axis and points are located exactly on them away from vertexes,
+/// so every point has the only projection. Results of splitting are expected to be equal
+/// to [KnownSplit::segments] within floating point precision.
+pub fn known_split(segments_len: Range<usize>, points_len: Range<usize>) -> impl Strategy<Value = KnownSplit> {
+    let segments_len = segments_len.start.max(1)..segments_len.end.max(2);
+    let points_len = points_len.start.max(2)..points_len.end.max(3);
+
+    vec((1.0..100.0f64, -100.0..100.0f64), segments_len)
+        .prop_map(|steps| {
+            let mut polyline = vec![Point(0.0, 0.0)];
+            for (dx, y) in steps {
+                let last = polyline[polyline.len() - 1];
+                polyline.push(Point(last.0 + dx, y));
+            }
+
+            polyline
+        })
+        .prop_flat_map(move |polyline| {
+            let segments_len = polyline.len() - 1;
+            let positions = vec((0..segments_len, 0.05..0.95f64), points_len.clone());
+
+            (Just(polyline), positions)
+        })
+        .prop_filter_map("points must be distinct", |(polyline, mut positions)| {
+            positions.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+            positions.dedup_by(|a, b| a.0 == b.0 && (a.1 - b.1).abs() < 1e-3);
+            if positions.len() < 2 {
+                return None;
+            }
+
+            let points: Vec<Point> = positions
+                .iter()
+                .map(|&(segment_index, ratio)| position(&polyline, segment_index, ratio))
+                .collect();
+
+            let segments = positions
+                .windows(2)
+                .zip(points.windows(2))
+                .map(|(p, s)| {
+                    let mut segment = vec![s[0]];
+                    segment.extend_from_slice(&polyline[p[0].0 + 1..p[1].0 + 1]);
+                    segment.push(s[1]);
+                    segment
+                })
+                .collect();
+
+            Some(KnownSplit { polyline, points, segments })
+        })
+}
+
+fn position(polyline: &[Point], segment_index: usize, ratio: f64) -> Point {
+    match polyline.len() {
+        0 => Point(0.0, 0.0),
+        1 => polyline[0],
+        _ => {
+            let a = polyline[segment_index];
+            let b = polyline[segment_index + 1];
+            Point(a.0 + ratio * (b.0 - a.0), a.1 + ratio * (b.1 - a.1))
+        }
+    }
+}
+
+/// Returns unit normal of the segment or zero vector if the segment is degenerate.
+fn normal(polyline: &[Point], segment_index: usize) -> (f64, f64) {
+    if polyline.len() < 2 {
+        return (0.0, 0.0);
+    }
+
+    let a = polyline[segment_index];
+    let b = polyline[segment_index + 1];
+    let length = a.distance_to(&b);
+    if length == 0.0 {
+        return (0.0, 0.0);
+    }
+
+    ((a.1 - b.1) / length, (b.0 - a.0) / length)
+}
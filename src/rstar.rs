@@ -0,0 +1,147 @@
+//! Integration with [rstar](https://docs.rs/rstar) enabled by `rstar` feature.
+//!
+//! Polyline segments can be stored in `RTree`, so splitting takes only segments
+//! within the distance threshold from points into account instead of scanning all of them.
+//! Existing trees of own segment types can be used implementing [IndexedSegment] for them.
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use rstar::primitives::GeomWithData;
+use rstar::{Envelope, RTree, RTreeNum, RTreeObject, PointDistance, AABB};
+
+use crate::euclidean::{Coordinate, Point};
+use crate::polysplit::{split, PolySplit, Result};
+
+/// Object of `RTree` that is a segment of the polyline between points `index` and `index + 1`.
+pub trait IndexedSegment {
+    /// Returns index of the segment in the polyline.
+    fn segment_index(&self) -> usize;
+}
+
+/// Segment of the polyline between points `index` and `index + 1`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment<T = f64> {
+    pub index: usize,
+    pub begin: Point<T>,
+    pub end: Point<T>,
+}
+
+impl<T: Coordinate + RTreeNum> RTreeObject for Segment<T> {
+    type Envelope = AABB<[T; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_corners([self.begin.0, self.begin.1], [self.end.0, self.end.1])
+    }
+}
+
+impl<T: Coordinate + RTreeNum> PointDistance for Segment<T> {
+    fn distance_2(&self, point: &[T; 2]) -> T {
+        let distance = Point(point[0], point[1]).distance_to_segment((&self.begin, &self.end)).distance;
+        distance * distance
+    }
+}
+
+impl<T> IndexedSegment for Segment<T> {
+    fn segment_index(&self) -> usize {
+        self.index
+    }
+}
+
+/// Geometry with the segment index as data, e.g. `GeomWithData<Line<[f64; 2]>, usize>`.
+impl<R: RTreeObject> IndexedSegment for GeomWithData<R, usize> {
+    fn segment_index(&self) -> usize {
+        self.data
+    }
+}
+
+/// Returns segments of the polyline.
+pub fn polyline_segments<T: Coordinate>(polyline: &[Point<T>]) -> Vec<Segment<T>> {
+    polyline
+        .windows(2)
+        .enumerate()
+        .map(|(index, s)| Segment { index, begin: s[0], end: s[1] })
+        .collect()
+}
+
+/// Returns `RTree` of polyline segments.
+pub fn segment_tree<T: Coordinate + RTreeNum>(polyline: &[Point<T>]) -> RTree<Segment<T>> {
+    RTree::bulk_load(polyline_segments(polyline))
+}
+
+/// Splits polyline into segments by the defined list of points
+/// taking candidate segments from `tree` built for the same polyline.
+///
+/// The tree can be of any [IndexedSegment] objects, points are converted to the points
+/// of the tree to query segments within the distance threshold, so the distance of the tree
+/// is expected to be the distance of [PolySplit]. Segments with indexes not belonging
+/// to the polyline are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::euclidean::Point;
+/// use polysplit::rstar::{polyline_split_indexed, segment_tree};
+///
+/// let polyline = vec![
+///     Point(0.0, 0.0),
+///     Point(10.0, 0.0),
+///     Point(20.0, 0.0),
+/// ];
+/// let points = vec![
+///     Point(1.0, 1.0),
+///     Point(19.0, 1.0),
+/// ];
+///
+/// let tree = segment_tree(&polyline);
+/// let segments = polyline_split_indexed(&polyline, &tree, &points, 2.0).unwrap();
+///
+/// assert_eq!(segments.len(), 1);
+/// ```
+///
+/// Existing tree of `rstar` lines with segment indexes:
+///
+/// ```
+/// use polysplit::euclidean::Point;
+/// use polysplit::rstar::polyline_split_indexed;
+/// use rstar::primitives::{GeomWithData, Line};
+/// use rstar::RTree;
+///
+/// let polyline = vec![Point(0.0, 0.0), Point(10.0, 0.0), Point(20.0, 0.0)];
+/// let lines = vec![
+///     GeomWithData::new(Line::new([0.0, 0.0], [10.0, 0.0]), 0),
+///     GeomWithData::new(Line::new([10.0, 0.0], [20.0, 0.0]), 1),
+/// ];
+///
+/// let tree = RTree::bulk_load(lines);
+/// let segments = polyline_split_indexed(&polyline, &tree, &[Point(1.0, 1.0), Point(19.0, 1.0)], 2.0).unwrap();
+///
+/// assert_eq!(segments.len(), 1);
+/// ```
+pub fn polyline_split_indexed<P, D, S>(
+    polyline: &[P],
+    tree: &RTree<S>,
+    points: &[P],
+    distance_threshold: D,
+) -> Result<Vec<Vec<P>>>
+where
+    P: PolySplit<D> + Debug + Into<<S::Envelope as Envelope>::Point>,
+    D: RTreeNum,
+    S: RTreeObject + PointDistance + IndexedSegment,
+    <S::Envelope as Envelope>::Point: rstar::Point<Scalar = D>,
+{
+    let segments_len = polyline.len().saturating_sub(1);
+    let mut candidates: Vec<Vec<usize>> = vec![Vec::new(); segments_len];
+
+    for (point_index, point) in points.iter().enumerate() {
+        let within = tree.locate_within_distance((*point).into(), distance_threshold * distance_threshold);
+        for segment in within {
+            if segment.segment_index() < segments_len {
+                candidates[segment.segment_index()].push(point_index);
+            }
+        }
+    }
+
+    split(polyline, points, Some(distance_threshold), None, Some(&candidates))
+}
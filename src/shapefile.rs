@@ -0,0 +1,482 @@
+//! [ESRI Shapefile](https://www.esri.com/content/dam/esrisites/sitecore-archive/Files/Pdfs/library/whitepapers/pdfs/shapefile.pdf)
+//! input and output enabled by `shapefile` feature.
+//!
+//! Polylines are read from `PolyLine` shapes and points from `Point` or `MultiPoint` shapes,
+//! their `Z` and `M` variants keep ordinates if the point type has them, shapes without
+//! measures or with "no data" ones are rejected if the point type has `m`. Shapes are matched
+//! by the key attribute of the DBF file. Segments are written as `PolyLine` shapes with
+//! `SOURCE_ID` and `SEGMENT` attributes.
+use alloc::collections::BTreeMap;
+use core::fmt::Debug;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::calendar::civil_from_days;
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, with_polyline_id, PolySplit, Result};
+
+const FILE_CODE: i32 = 9994;
+const VERSION: i32 = 1000;
+const HEADER_LEN: usize = 100;
+
+const NULL_SHAPE: i32 = 0;
+const POINT: i32 = 1;
+const POLYLINE: i32 = 3;
+const MULTI_POINT: i32 = 8;
+
+/// Values less than or equal to that are "no data" measures, reference writers use it exactly.
+const NO_DATA: f64 = -1e38;
+
+/// Reads polylines from `PolyLine` shapes by the key attribute,
+/// every key and shape can have only one polyline.
+pub fn read_polylines<P: Ordinates<f64>>(shp: impl Read, dbf: impl Read, key: &str) -> Result<BTreeMap<String, Vec<P>>> {
+    let mut polylines = BTreeMap::new();
+
+    for (id, parts) in read_shapes(shp, dbf, key, &[POLYLINE])? {
+        let mut parts = parts.into_iter();
+        let polyline = match (parts.next(), parts.next()) {
+            (Some(polyline), None) => polyline,
+            _ => return Err(invalid_format(&format!("polyline {} must have one part", id))),
+        };
+
+        if polylines.insert(id.clone(), polyline).is_some() {
+            return Err(invalid_format(&format!("polyline {} is duplicated", id)));
+        }
+    }
+
+    Ok(polylines)
+}
+
+/// Reads points from `Point` and `MultiPoint` shapes by the key attribute,
+/// points of the same key are joined in the order of shapes.
+pub fn read_points<P: Ordinates<f64>>(shp: impl Read, dbf: impl Read, key: &str) -> Result<BTreeMap<String, Vec<P>>> {
+    let mut points: BTreeMap<String, Vec<P>> = BTreeMap::new();
+
+    for (id, parts) in read_shapes(shp, dbf, key, &[POINT, MULTI_POINT])? {
+        points.entry(id).or_default().extend(parts.into_iter().flatten());
+    }
+
+    Ok(points)
+}
+
+/// Writes segments as `PolyLine` shapes with `SOURCE_ID` attribute of the polyline key
+/// and `SEGMENT` attribute of the segment index.
+///
+/// `PolyLineZ` is written if the point type has `z`, `PolyLineM` if it has `m` only.
+pub fn write_segments<P: Ordinates<f64>>(
+    mut shp: impl Write,
+    mut shx: impl Write,
+    mut dbf: impl Write,
+    segments: &BTreeMap<String, Vec<Vec<P>>>,
+) -> Result<()> {
+    let shape_type = match (P::HAS_Z, P::HAS_M) {
+        (true, _) => POLYLINE + 10,
+        (false, true) => POLYLINE + 20,
+        (false, false) => POLYLINE,
+    };
+
+    let mut records = Vec::new();
+    let mut offsets = Vec::new();
+    let mut attributes = Vec::new();
+
+    for (id, polyline_segments) in segments {
+        for (segment_index, segment) in polyline_segments.iter().enumerate() {
+            let content = polyline_content(shape_type, segment);
+
+            offsets.push(((HEADER_LEN + records.len()) / 2, content.len() / 2));
+            records.extend_from_slice(&(offsets.len() as i32).to_be_bytes());
+            records.extend_from_slice(&((content.len() / 2) as i32).to_be_bytes());
+            records.extend_from_slice(&content);
+
+            attributes.push((id.as_str(), segment_index));
+        }
+    }
+
+    let points = segments.values().flatten().flatten();
+    let bounds = [
+        range(points.clone().map(|p| p.x())),
+        range(points.clone().map(|p| p.y())),
+        range(points.clone().filter_map(|p| p.z())),
+        range(points.filter_map(|p| p.m())),
+    ];
+
+    shp.write_all(&header(shape_type, HEADER_LEN + records.len(), bounds))?;
+    shp.write_all(&records)?;
+
+    shx.write_all(&header(shape_type, HEADER_LEN + offsets.len() * 8, bounds))?;
+    for (offset, len) in offsets {
+        shx.write_all(&(offset as i32).to_be_bytes())?;
+        shx.write_all(&(len as i32).to_be_bytes())?;
+    }
+
+    dbf.write_all(&dbf_content(&attributes)?)?;
+    Ok(())
+}
+
+/// Splits polylines of `.shp` and `.dbf` files by the points with the same key attribute,
+/// and writes `.shp`, `.shx` and `.dbf` files of the segments.
+pub fn shapefile_split<P>(
+    polylines: impl AsRef<Path>,
+    points: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    key: &str,
+    distance_threshold: Option<f64>,
+) -> Result<()>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let (polylines, points, output) = (polylines.as_ref(), points.as_ref(), output.as_ref());
+
+    let polylines: BTreeMap<String, Vec<P>> = read_polylines(
+        File::open(polylines.with_extension("shp"))?,
+        File::open(polylines.with_extension("dbf"))?,
+        key,
+    )?;
+    let points: BTreeMap<String, Vec<P>> = read_points(
+        File::open(points.with_extension("shp"))?,
+        File::open(points.with_extension("dbf"))?,
+        key,
+    )?;
+
+    let segments = polylines
+        .into_iter()
+        .map(|(id, polyline)| {
+            let points = points.get(&id).map(Vec::as_slice).unwrap_or_default();
+
+            match polyline_split(&polyline, points, distance_threshold) {
+                Ok(segments) => Ok((id, segments)),
+                Err(error) => Err(with_polyline_id(error, &id)),
+            }
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    let mut shp = BufWriter::new(File::create(output.with_extension("shp"))?);
+    let mut shx = BufWriter::new(File::create(output.with_extension("shx"))?);
+    let mut dbf = BufWriter::new(File::create(output.with_extension("dbf"))?);
+
+    write_segments(&mut shp, &mut shx, &mut dbf, &segments)?;
+
+    shp.flush()?;
+    shx.flush()?;
+    dbf.flush()?;
+    Ok(())
+}
+
+/// Reads parts of the shapes of the expected types with their key attribute,
+/// null and deleted shapes are skipped.
+fn read_shapes<P: Ordinates<f64>>(
+    mut shp: impl Read,
+    mut dbf: impl Read,
+    key: &str,
+    shape_types: &[i32],
+) -> Result<Vec<(String, Vec<Vec<P>>)>> {
+    let mut shp_content = Vec::new();
+    shp.read_to_end(&mut shp_content)?;
+    let mut dbf_content = Vec::new();
+    dbf.read_to_end(&mut dbf_content)?;
+
+    let keys = read_keys(&dbf_content, key)?;
+
+    let mut reader = Reader::new(&shp_content);
+    if reader.i32_be()? != FILE_CODE {
+        return Err(invalid_format("invalid shapefile file code"));
+    }
+    reader.bytes(HEADER_LEN - 4)?;
+
+    let mut shapes = Vec::new();
+    let mut keys = keys.into_iter();
+    while !reader.is_empty() {
+        let _record_number = reader.i32_be()?;
+        let content_len = reader.i32_be()?;
+        let content = reader.bytes(usize::try_from(content_len).unwrap_or(usize::MAX).saturating_mul(2))?;
+
+        let key = keys.next().ok_or_else(|| invalid_format("DBF file has less records than shapefile"))?;
+        let parts = read_shape(content, shape_types)?;
+
+        if let (Some(key), Some(parts)) = (key, parts) {
+            shapes.push((key, parts));
+        }
+    }
+
+    Ok(shapes)
+}
+
+fn read_shape<P: Ordinates<f64>>(content: &[u8], shape_types: &[i32]) -> Result<Option<Vec<Vec<P>>>> {
+    let mut reader = Reader::new(content);
+
+    let shape_type = reader.i32_le()?;
+    if shape_type == NULL_SHAPE {
+        return Ok(None);
+    }
+
+    let (base_type, has_z, has_m) = match shape_type / 10 {
+        0 => (shape_type, false, false),
+        1 => (shape_type - 10, true, true),
+        2 => (shape_type - 20, false, true),
+        _ => (shape_type, false, false),
+    };
+
+    if !shape_types.contains(&base_type) {
+        return Err(invalid_format(&format!("unexpected shape type {}", shape_type)));
+    }
+
+    if base_type == POINT {
+        let (x, y) = (reader.f64_le()?, reader.f64_le()?);
+        let z = if has_z { Some(reader.f64_le()?) } else { None };
+        // Measure is optional for `PointZ`
+        let m = if has_m && !reader.is_empty() { Some(reader.f64_le()?) } else { None };
+        check_measures::<P>(m.as_ref().map(core::slice::from_ref))?;
+
+        return Ok(Some(vec![vec![P::from_ordinates(x, y, z, m)]]));
+    }
+
+    // Bounding box
+    reader.bytes(32)?;
+
+    let (parts, len) = if base_type == POLYLINE {
+        let parts_len = reader.len()?;
+        let len = reader.len()?;
+        let parts = (0..parts_len).map(|_| reader.len()).collect::<Result<Vec<_>>>()?;
+        (parts, len)
+    } else {
+        let len = reader.len()?;
+        (vec![0], len)
+    };
+
+    let xy = (0..len)
+        .map(|_| Ok((reader.f64_le()?, reader.f64_le()?)))
+        .collect::<Result<Vec<_>>>()?;
+    let z = optional_ordinates(&mut reader, has_z, len)?;
+    let m = optional_ordinates(&mut reader, has_m, len)?;
+    check_measures::<P>(m.as_deref())?;
+
+    let point = |i: usize| {
+        let (x, y) = xy[i];
+        P::from_ordinates(x, y, z.as_ref().map(|z| z[i]), m.as_ref().map(|m| m[i]))
+    };
+
+    let ends = parts.iter().skip(1).copied().chain(Some(len));
+    parts
+        .iter()
+        .zip(ends)
+        .map(|(&begin, end)| {
+            if begin > end || end > len {
+                return Err(invalid_format("invalid part index"));
+            }
+
+            Ok((begin..end).map(point).collect())
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(Some)
+}
+
+/// Reads ordinate range and values, they are optional if there is no more content.
+fn optional_ordinates(reader: &mut Reader, is_defined: bool, len: usize) -> Result<Option<Vec<f64>>> {
+    if !is_defined || reader.is_empty() {
+        return Ok(None);
+    }
+
+    // Range
+    reader.bytes(16)?;
+    (0..len).map(|_| reader.f64_le()).collect::<Result<Vec<_>>>().map(Some)
+}
+
+/// Checks that measures are defined if the point type has `m`, missing and "no data" measures
+/// are rejected rather than read as real ones.
+fn check_measures<P: Ordinates<f64>>(measures: Option<&[f64]>) -> Result<()> {
+    if P::HAS_M && measures.is_none_or(|measures| measures.iter().any(|&m| m <= NO_DATA)) {
+        return Err(invalid_format("measures are expected for the point type with m"));
+    }
+
+    Ok(())
+}
+
+/// Reads values of the key attribute by DBF records, `None` for deleted records.
+fn read_keys(dbf: &[u8], key: &str) -> Result<Vec<Option<String>>> {
+    let mut reader = Reader::new(dbf);
+    let header = reader.bytes(32)?;
+
+    let records_len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+    let header_len = u16::from_le_bytes([header[8], header[9]]) as usize;
+    let record_len = u16::from_le_bytes([header[10], header[11]]) as usize;
+
+    let mut field = None;
+    let mut offset = 1;
+    while reader.position < header_len.saturating_sub(1) && dbf.get(reader.position) != Some(&0x0d) {
+        let descriptor = reader.bytes(32)?;
+        let name_len = descriptor[..11].iter().position(|&b| b == 0).unwrap_or(11);
+        let len = descriptor[16] as usize;
+
+        if field.is_none() && descriptor[..name_len].eq_ignore_ascii_case(key.as_bytes()) {
+            field = Some((offset, len));
+        }
+        offset += len;
+    }
+
+    let (offset, len) = field.ok_or_else(|| invalid_format(&format!("key attribute {} is not found", key)))?;
+    if offset + len > record_len {
+        return Err(invalid_format("invalid DBF record length"));
+    }
+
+    (0..records_len)
+        .map(|index| {
+            let record = dbf
+                .get(header_len + index * record_len..)
+                .and_then(|record| record.get(..record_len))
+                .ok_or_else(|| invalid_format("unexpected end of DBF file"))?;
+
+            if record[0] == b'*' {
+                return Ok(None);
+            }
+
+            Ok(Some(String::from_utf8_lossy(&record[offset..offset + len]).trim().to_string()))
+        })
+        .collect()
+}
+
+fn polyline_content<P: Ordinates<f64>>(shape_type: i32, segment: &[P]) -> Vec<u8> {
+    let x = range(segment.iter().map(|p| p.x()));
+    let y = range(segment.iter().map(|p| p.y()));
+    let z = range(segment.iter().filter_map(|p| p.z()));
+    let m = range(segment.iter().filter_map(|p| p.m()));
+
+    let mut content = Vec::new();
+    content.extend_from_slice(&shape_type.to_le_bytes());
+    push_f64(&mut content, [x.0, y.0, x.1, y.1]);
+
+    // One part starting at the first point
+    for value in [1, segment.len() as i32, 0] {
+        content.extend_from_slice(&value.to_le_bytes());
+    }
+
+    push_f64(&mut content, segment.iter().flat_map(|p| [p.x(), p.y()]));
+    if P::HAS_Z {
+        push_f64(&mut content, [z.0, z.1].into_iter().chain(segment.iter().filter_map(|p| p.z())));
+    }
+    if P::HAS_M {
+        push_f64(&mut content, [m.0, m.1].into_iter().chain(segment.iter().filter_map(|p| p.m())));
+    }
+
+    content
+}
+
+fn header(shape_type: i32, file_len: usize, bounds: [(f64, f64); 4]) -> Vec<u8> {
+    let [x, y, z, m] = bounds;
+
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(&FILE_CODE.to_be_bytes());
+    header.extend_from_slice(&[0; 20]);
+    header.extend_from_slice(&((file_len / 2) as i32).to_be_bytes());
+    header.extend_from_slice(&VERSION.to_le_bytes());
+    header.extend_from_slice(&shape_type.to_le_bytes());
+    push_f64(&mut header, [x.0, y.0, x.1, y.1, z.0, z.1, m.0, m.1]);
+
+    header
+}
+
+fn push_f64(content: &mut Vec<u8>, values: impl IntoIterator<Item = f64>) {
+    for value in values {
+        content.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn dbf_content(attributes: &[(&str, usize)]) -> Result<Vec<u8>> {
+    const SEGMENT_LEN: usize = 10;
+
+    let id_len = attributes.iter().map(|(id, _)| id.len()).max().unwrap_or(0).max(1);
+    if id_len > 254 {
+        return Err(invalid_format("polyline id is longer than 254 bytes"));
+    }
+
+    let header_len = 32 + 2 * 32 + 1;
+    let record_len = 1 + id_len + SEGMENT_LEN;
+
+    let days = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs() / 86400);
+    let (year, month, day) = civil_from_days(days as i64);
+
+    let mut content = Vec::new();
+    // dBase III with the last update date
+    content.extend_from_slice(&[0x03, (year - 1900).clamp(0, 255) as u8, month as u8, day as u8]);
+    content.extend_from_slice(&(attributes.len() as u32).to_le_bytes());
+    content.extend_from_slice(&(header_len as u16).to_le_bytes());
+    content.extend_from_slice(&(record_len as u16).to_le_bytes());
+    content.extend_from_slice(&[0; 20]);
+
+    for (name, field_type, len) in [("SOURCE_ID", b'C', id_len), ("SEGMENT", b'N', SEGMENT_LEN)] {
+        let mut descriptor = [0; 32];
+        descriptor[..name.len()].copy_from_slice(name.as_bytes());
+        descriptor[11] = field_type;
+        descriptor[16] = len as u8;
+        content.extend_from_slice(&descriptor);
+    }
+    content.push(0x0d);
+
+    // Field lengths are in bytes, so ids are padded by bytes rather than chars
+    for (id, segment_index) in attributes {
+        content.push(b' ');
+        content.extend_from_slice(id.as_bytes());
+        content.resize(content.len() + id_len - id.len(), b' ');
+        content.extend_from_slice(format!("{:>SEGMENT_LEN$}", segment_index).as_bytes());
+    }
+    content.push(0x1a);
+
+    Ok(content)
+}
+
+fn range(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values
+        .fold(None, |range: Option<(f64, f64)>, value| match range {
+            Some((min, max)) => Some((min.min(value), max.max(value))),
+            None => Some((value, value)),
+        })
+        .unwrap_or((0.0, 0.0))
+}
+
+struct Reader<'a> {
+    content: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(content: &'a [u8]) -> Self {
+        Reader { content, position: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position >= self.content.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .content
+            .get(self.position..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or_else(|| invalid_format("unexpected end of shapefile"))?;
+
+        self.position += len;
+        Ok(bytes)
+    }
+
+    fn i32_be(&mut self) -> Result<i32> {
+        let bytes = self.bytes(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn i32_le(&mut self) -> Result<i32> {
+        let bytes = self.bytes(4)?;
+        Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn f64_le(&mut self) -> Result<f64> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.bytes(8)?);
+        Ok(f64::from_le_bytes(bytes))
+    }
+
+    /// Reads non-negative number of parts, points or index.
+    fn len(&mut self) -> Result<usize> {
+        usize::try_from(self.i32_le()?).map_err(|_| invalid_format("negative number in shape"))
+    }
+}
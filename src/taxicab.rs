@@ -0,0 +1,96 @@
+use crate::euclidean::Coordinate;
+use crate::polysplit::{PolySplit, CutRatioResult, DistanceToSegmentResult};
+
+/// Point `(x, y)` measuring distances by taxicab (Manhattan, L1) metric.
+///
+/// Taxicab distance to a segment can be minimal along a whole part of it,
+/// in this case the closest to the segment start projection is chosen.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Point<T = f64>(pub T, pub T);
+
+impl<T: Coordinate> Point<T> {
+    pub fn distance_to(&self, to: &Point<T>) -> T {
+        (self.0 - to.0).abs() + (self.1 - to.1).abs()
+    }
+}
+
+impl<T: Coordinate> PolySplit<T> for Point<T> {
+    fn distance_to_point(&self, point: &Self) -> T {
+        self.distance_to(point)
+    }
+
+    fn distance_to_segment(&self, s: (&Point<T>, &Point<T>)) -> DistanceToSegmentResult<Point<T>, T> {
+        self.distance_to_segment_with_tolerance(s, T::DEGENERATE_TOLERANCE)
+    }
+
+    fn distance_to_segment_with_tolerance(
+        &self,
+        s: (&Point<T>, &Point<T>),
+        tolerance: T,
+    ) -> DistanceToSegmentResult<Point<T>, T> {
+        let segment_distance = s.0.distance_to(s.1);
+        if segment_distance <= tolerance {
+            return DistanceToSegmentResult{
+                distance: self.distance_to(s.0),
+                cut_point: *s.0,
+                cut_ratio: CutRatioResult::Begin,
+            };
+        }
+
+        let vx = s.1.0 - s.0.0;
+        let vy = s.1.1 - s.0.1;
+
+        let ux = self.0 - s.0.0;
+        let uy = self.1 - s.0.1;
+
+        // Distance is piecewise linear convex function of the ratio,
+        // so the minimum is reached at the ends or where one of the axes matches
+        let mut candidates = [T::zero(), T::one(), T::zero(), T::zero()];
+        if vx != T::zero() {
+            candidates[2] = (ux / vx).max(T::zero()).min(T::one());
+        }
+        if vy != T::zero() {
+            candidates[3] = (uy / vy).max(T::zero()).min(T::one());
+        }
+
+        let distance_at = |ratio: T| (ux - ratio * vx).abs() + (uy - ratio * vy).abs();
+
+        let mut cut_ratio = T::zero();
+        let mut distance = distance_at(cut_ratio);
+        for ratio in candidates {
+            let candidate_distance = distance_at(ratio);
+            if candidate_distance < distance || (candidate_distance == distance && ratio < cut_ratio) {
+                cut_ratio = ratio;
+                distance = candidate_distance;
+            }
+        }
+
+        if cut_ratio <= T::zero() {
+            DistanceToSegmentResult{
+                distance: self.distance_to(s.0),
+                cut_point: *s.0,
+                cut_ratio: CutRatioResult::Begin,
+            }
+        } else if cut_ratio >= T::one() {
+            DistanceToSegmentResult{
+                distance: self.distance_to(s.1),
+                cut_point: *s.1,
+                cut_ratio: CutRatioResult::End,
+            }
+        } else {
+            let cut_point = Point(s.0.0 + cut_ratio * vx, s.0.1 + cut_ratio * vy);
+
+            DistanceToSegmentResult{
+                distance: self.distance_to(&cut_point),
+                cut_point,
+                cut_ratio: CutRatioResult::Medium(cut_ratio.into()),
+            }
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.0.is_finite() && self.1.is_finite()
+    }
+}
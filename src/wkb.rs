@@ -0,0 +1,296 @@
+//! [WKB](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry#Well-known_binary)
+//! and PostGIS EWKB reading and writing enabled by `wkb` feature.
+//!
+//! Polylines are read from `LineString` and points from `MultiPoint`, segments are written
+//! as `MultiLineString`. Both byte orders, ISO (`1000`, `2000`, `3000` type offsets) and EWKB
+//! (flags) `Z`/`M` ordinates and EWKB SRID are supported when reading.
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, PolySplit, Result};
+
+const POINT: u32 = 1;
+const LINE_STRING: u32 = 2;
+const MULTI_POINT: u32 = 4;
+const MULTI_LINE_STRING: u32 = 5;
+
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// Byte order of WKB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// XDR, big endian.
+    BigEndian,
+    /// NDR, little endian.
+    LittleEndian,
+}
+
+/// Reads polyline from `LineString`.
+pub fn read_line_string<P: Ordinates<f64>>(wkb: &[u8]) -> Result<Vec<P>> {
+    let mut reader = Reader::new(wkb);
+    let header = reader.header(LINE_STRING)?;
+
+    let len = reader.u32()?;
+    let points = (0..len)
+        .map(|_| reader.point(header))
+        .collect::<Result<Vec<P>>>()?;
+
+    reader.expect_end()?;
+    Ok(points)
+}
+
+/// Reads points from `MultiPoint`.
+pub fn read_multi_point<P: Ordinates<f64>>(wkb: &[u8]) -> Result<Vec<P>> {
+    let mut reader = Reader::new(wkb);
+    let header = reader.header(MULTI_POINT)?;
+
+    let len = reader.u32()?;
+    let points = (0..len)
+        .map(|_| {
+            let point_header = reader.header(POINT)?;
+            if (point_header.has_z, point_header.has_m) != (header.has_z, header.has_m) {
+                return Err(invalid_format("point dimension differs from multi point one"));
+            }
+
+            reader.point(point_header)
+        })
+        .collect::<Result<Vec<P>>>()?;
+
+    reader.expect_end()?;
+    Ok(points)
+}
+
+/// Reads EWKB SRID of the geometry, `None` if it is not defined.
+pub fn read_srid(wkb: &[u8]) -> Result<Option<u32>> {
+    let mut reader = Reader::new(wkb);
+    reader.byte_order()?;
+
+    let geometry_type = reader.u32()?;
+    if geometry_type & EWKB_SRID == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(reader.u32()?))
+}
+
+/// Writes segments as `MultiLineString`.
+///
+/// ISO WKB is written if `srid` is `None`, otherwise EWKB with SRID.
+pub fn write_multi_line_string<P: Ordinates<f64>>(
+    segments: &[Vec<P>],
+    byte_order: ByteOrder,
+    srid: Option<u32>,
+) -> Vec<u8> {
+    let mut writer = Writer { wkb: Vec::new(), byte_order };
+
+    writer.header::<P>(MULTI_LINE_STRING, srid.is_some(), srid);
+    writer.u32(segments.len() as u32);
+
+    for segment in segments {
+        writer.header::<P>(LINE_STRING, srid.is_some(), None);
+        writer.u32(segment.len() as u32);
+
+        for point in segment {
+            writer.f64(point.x());
+            writer.f64(point.y());
+            if let Some(z) = point.z() {
+                writer.f64(z);
+            }
+            if let Some(m) = point.m() {
+                writer.f64(m);
+            }
+        }
+    }
+
+    writer.wkb
+}
+
+/// Splits `LineString` by `MultiPoint` and returns segments as `MultiLineString`
+/// with byte order and SRID of the line string.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::euclidean::Point;
+/// use polysplit::wkb::wkb_split;
+///
+/// let ordinates = |ordinates: &[f64]| -> Vec<u8> {
+///     ordinates.iter().flat_map(|value| value.to_le_bytes()).collect()
+/// };
+///
+/// // Little endian `LineString` of 2 points
+/// let line_string = [vec![1, 2, 0, 0, 0, 2, 0, 0, 0], ordinates(&[0.0, 0.0, 10.0, 0.0])].concat();
+///
+/// // Little endian `MultiPoint` of 2 points
+/// let points = [
+///     vec![1, 4, 0, 0, 0, 2, 0, 0, 0],
+///     vec![1, 1, 0, 0, 0], ordinates(&[1.0, 1.0]),
+///     vec![1, 1, 0, 0, 0], ordinates(&[9.0, 1.0]),
+/// ].concat();
+///
+/// let segments = wkb_split::<Point>(&line_string, &points, None).unwrap();
+///
+/// assert_eq!(segments[..9], [1, 5, 0, 0, 0, 1, 0, 0, 0]);
+/// ```
+pub fn wkb_split<P>(line_string: &[u8], points: &[u8], distance_threshold: Option<f64>) -> Result<Vec<u8>>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let byte_order = Reader::new(line_string).byte_order()?;
+    let srid = read_srid(line_string)?;
+
+    let polyline: Vec<P> = read_line_string(line_string)?;
+    let points: Vec<P> = read_multi_point(points)?;
+    let segments = polyline_split(&polyline, &points, distance_threshold)?;
+
+    Ok(write_multi_line_string(&segments, byte_order, srid))
+}
+
+#[derive(Clone, Copy)]
+struct Header {
+    has_z: bool,
+    has_m: bool,
+}
+
+struct Reader<'a> {
+    wkb: &'a [u8],
+    byte_order: ByteOrder,
+}
+
+impl<'a> Reader<'a> {
+    fn new(wkb: &'a [u8]) -> Self {
+        Reader { wkb, byte_order: ByteOrder::LittleEndian }
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        if self.wkb.len() < N {
+            return Err(invalid_format("unexpected end of WKB"));
+        }
+
+        let (bytes, rest) = self.wkb.split_at(N);
+        self.wkb = rest;
+
+        let mut result = [0; N];
+        result.copy_from_slice(bytes);
+        Ok(result)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.bytes()?;
+        Ok(match self.byte_order {
+            ByteOrder::BigEndian => u32::from_be_bytes(bytes),
+            ByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+        })
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        let bytes = self.bytes()?;
+        Ok(match self.byte_order {
+            ByteOrder::BigEndian => f64::from_be_bytes(bytes),
+            ByteOrder::LittleEndian => f64::from_le_bytes(bytes),
+        })
+    }
+
+    fn byte_order(&mut self) -> Result<ByteOrder> {
+        self.byte_order = match self.bytes::<1>()? {
+            [0] => ByteOrder::BigEndian,
+            [1] => ByteOrder::LittleEndian,
+            [value] => return Err(invalid_format(&format!("unknown byte order {}", value))),
+        };
+
+        Ok(self.byte_order)
+    }
+
+    /// Reads byte order, geometry type and optional SRID checking that geometry type is expected.
+    fn header(&mut self, expected_type: u32) -> Result<Header> {
+        self.byte_order()?;
+
+        let value = self.u32()?;
+        if value & EWKB_SRID != 0 {
+            self.u32()?;
+        }
+
+        let ewkb_z = value & EWKB_Z != 0;
+        let ewkb_m = value & EWKB_M != 0;
+        let value = value & !(EWKB_Z | EWKB_M | EWKB_SRID);
+        let (geometry_type, iso_dimension) = (value % 1000, value / 1000);
+
+        if geometry_type != expected_type {
+            return Err(invalid_format(&format!(
+                "geometry type {} is expected, got {}",
+                expected_type, geometry_type
+            )));
+        }
+
+        match (iso_dimension, ewkb_z || ewkb_m) {
+            (0, _) => Ok(Header { has_z: ewkb_z, has_m: ewkb_m }),
+            (1..=3, false) => Ok(Header { has_z: iso_dimension & 1 != 0, has_m: iso_dimension & 2 != 0 }),
+            _ => Err(invalid_format(&format!("unknown dimension of geometry type {}", value))),
+        }
+    }
+
+    fn point<P: Ordinates<f64>>(&mut self, header: Header) -> Result<P> {
+        let x = self.f64()?;
+        let y = self.f64()?;
+        let z = if header.has_z { Some(self.f64()?) } else { None };
+        let m = if header.has_m { Some(self.f64()?) } else { None };
+
+        Ok(P::from_ordinates(x, y, z, m))
+    }
+
+    fn expect_end(&self) -> Result<()> {
+        if self.wkb.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid_format("unexpected bytes after geometry"))
+        }
+    }
+}
+
+struct Writer {
+    wkb: Vec<u8>,
+    byte_order: ByteOrder,
+}
+
+impl Writer {
+    fn u32(&mut self, value: u32) {
+        match self.byte_order {
+            ByteOrder::BigEndian => self.wkb.extend_from_slice(&value.to_be_bytes()),
+            ByteOrder::LittleEndian => self.wkb.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    fn f64(&mut self, value: f64) {
+        match self.byte_order {
+            ByteOrder::BigEndian => self.wkb.extend_from_slice(&value.to_be_bytes()),
+            ByteOrder::LittleEndian => self.wkb.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    /// Writes byte order, geometry type and SRID if it is defined.
+    fn header<P: Ordinates<f64>>(&mut self, geometry_type: u32, is_ewkb: bool, srid: Option<u32>) {
+        self.wkb.push(match self.byte_order {
+            ByteOrder::BigEndian => 0,
+            ByteOrder::LittleEndian => 1,
+        });
+
+        if !is_ewkb {
+            let dimension = if P::HAS_Z { 1000 } else { 0 } + if P::HAS_M { 2000 } else { 0 };
+            self.u32(geometry_type + dimension);
+            return;
+        }
+
+        let flags = if P::HAS_Z { EWKB_Z } else { 0 } | if P::HAS_M { EWKB_M } else { 0 };
+        match srid {
+            Some(srid) => {
+                self.u32(geometry_type | flags | EWKB_SRID);
+                self.u32(srid);
+            }
+            None => self.u32(geometry_type | flags),
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! [WKT](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry)
+//! reading and writing enabled by `wkt` feature.
+//!
+//! Polylines are read from `LINESTRING` and points from `MULTIPOINT`, segments are written
+//! as `MULTILINESTRING`. `Z`, `M` and `ZM` ordinates are read and written if the point type
+//! has them, EWKT `SRID=...;` prefix is skipped when reading.
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Write};
+
+use crate::euclidean::Ordinates;
+use crate::polysplit::{invalid_format, polyline_split, PolySplit, Result};
+
+/// Reads polyline from `LINESTRING`.
+pub fn read_line_string<P: Ordinates<f64>>(wkt: &str) -> Result<Vec<P>> {
+    let mut parser = Parser::new(wkt);
+    parser.skip_srid();
+    parser.expect_keyword("LINESTRING")?;
+
+    let dimension = parser.dimension();
+    let points = if parser.empty() {
+        Vec::new()
+    } else {
+        parser.coordinates(dimension, false)?
+    };
+
+    parser.expect_end()?;
+    Ok(points)
+}
+
+/// Reads points from `MULTIPOINT`, both `MULTIPOINT (1 2, 3 4)`
+/// and `MULTIPOINT ((1 2), (3 4))` notations are supported.
+pub fn read_multi_point<P: Ordinates<f64>>(wkt: &str) -> Result<Vec<P>> {
+    let mut parser = Parser::new(wkt);
+    parser.skip_srid();
+    parser.expect_keyword("MULTIPOINT")?;
+
+    let dimension = parser.dimension();
+    let points = if parser.empty() {
+        Vec::new()
+    } else {
+        parser.coordinates(dimension, true)?
+    };
+
+    parser.expect_end()?;
+    Ok(points)
+}
+
+/// Writes segments as `MULTILINESTRING`.
+pub fn write_multi_line_string<P: Ordinates<f64>>(segments: &[Vec<P>]) -> String {
+    let mut wkt = String::from("MULTILINESTRING");
+    match (P::HAS_Z, P::HAS_M) {
+        (true, true) => wkt.push_str(" ZM"),
+        (true, false) => wkt.push_str(" Z"),
+        (false, true) => wkt.push_str(" M"),
+        (false, false) => {}
+    }
+
+    if segments.is_empty() {
+        wkt.push_str(" EMPTY");
+        return wkt;
+    }
+
+    wkt.push_str(" (");
+    for (segment_index, segment) in segments.iter().enumerate() {
+        if segment_index > 0 {
+            wkt.push_str(", ");
+        }
+
+        wkt.push('(');
+        for (point_index, point) in segment.iter().enumerate() {
+            if point_index > 0 {
+                wkt.push_str(", ");
+            }
+
+            // Writing to `String` cannot fail
+            let _ = write!(wkt, "{} {}", point.x(), point.y());
+            if let Some(z) = point.z() {
+                let _ = write!(wkt, " {}", z);
+            }
+            if let Some(m) = point.m() {
+                let _ = write!(wkt, " {}", m);
+            }
+        }
+        wkt.push(')');
+    }
+    wkt.push(')');
+
+    wkt
+}
+
+/// Splits `LINESTRING` by `MULTIPOINT` and returns segments as `MULTILINESTRING`.
+///
+/// # Examples
+///
+/// ```
+/// use polysplit::euclidean::Point;
+/// use polysplit::wkt::wkt_split;
+///
+/// let segments = wkt_split::<Point>(
+///     "LINESTRING (0 0, 10 0, 20 0)",
+///     "MULTIPOINT ((1 1), (19 1))",
+///     None,
+/// ).unwrap();
+///
+/// assert_eq!(segments, "MULTILINESTRING ((1 0, 10 0, 19 0))");
+/// ```
+pub fn wkt_split<P>(line_string: &str, points: &str, distance_threshold: Option<f64>) -> Result<String>
+where
+    P: PolySplit<f64> + Ordinates<f64> + Debug,
+{
+    let polyline: Vec<P> = read_line_string(line_string)?;
+    let points: Vec<P> = read_multi_point(points)?;
+    let segments = polyline_split(&polyline, &points, distance_threshold)?;
+
+    Ok(write_multi_line_string(&segments))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Dimension {
+    /// Dimension is defined by the number of ordinates.
+    Implicit,
+    Z,
+    M,
+    ZM,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser { input, position: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    fn word(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    fn try_keyword(&mut self, keyword: &str) -> bool {
+        let position = self.position;
+        if self.word().eq_ignore_ascii_case(keyword) {
+            return true;
+        }
+
+        self.position = position;
+        false
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.try_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(invalid_format(&format!("{} is expected", keyword)))
+        }
+    }
+
+    fn expect_char(&mut self, c: char) -> Result<()> {
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            Ok(())
+        } else {
+            Err(invalid_format(&format!("'{}' is expected at position {}", c, self.position)))
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<()> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(invalid_format(&format!("unexpected input at position {}", self.position))),
+        }
+    }
+
+    fn skip_srid(&mut self) {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if rest.len() >= 5 && rest[..5].eq_ignore_ascii_case("SRID=") {
+            if let Some(end) = rest.find(';') {
+                self.position += end + 1;
+            }
+        }
+    }
+
+    fn dimension(&mut self) -> Dimension {
+        if self.try_keyword("ZM") {
+            Dimension::ZM
+        } else if self.try_keyword("Z") {
+            Dimension::Z
+        } else if self.try_keyword("M") {
+            Dimension::M
+        } else {
+            Dimension::Implicit
+        }
+    }
+
+    fn empty(&mut self) -> bool {
+        self.try_keyword("EMPTY")
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E')))
+            .unwrap_or(rest.len());
+        let value = rest[..len].parse().ok()?;
+        self.position += len;
+        Some(value)
+    }
+
+    /// Reads list of coordinates, each of them can be in parentheses if `allow_parentheses` is set.
+    fn coordinates<P: Ordinates<f64>>(&mut self, dimension: Dimension, allow_parentheses: bool) -> Result<Vec<P>> {
+        let mut points = Vec::new();
+        let mut ordinates_len = None;
+
+        self.expect_char('(')?;
+        loop {
+            let is_parenthesized = allow_parentheses && self.peek() == Some('(');
+            if is_parenthesized {
+                self.expect_char('(')?;
+            }
+
+            let mut ordinates = Vec::with_capacity(4);
+            while let Some(value) = self.number() {
+                ordinates.push(value);
+            }
+
+            if ordinates_len.is_some_and(|len| len != ordinates.len()) {
+                return Err(invalid_format("coordinates have different number of ordinates"));
+            }
+            ordinates_len = Some(ordinates.len());
+
+            points.push(point(&ordinates, dimension)?);
+
+            if is_parenthesized {
+                self.expect_char(')')?;
+            }
+
+            if self.peek() == Some(',') {
+                self.expect_char(',')?;
+            } else {
+                break;
+            }
+        }
+        self.expect_char(')')?;
+
+        Ok(points)
+    }
+}
+
+fn point<P: Ordinates<f64>>(ordinates: &[f64], dimension: Dimension) -> Result<P> {
+    let point = match (dimension, ordinates) {
+        (Dimension::Implicit, &[x, y]) => P::from_ordinates(x, y, None, None),
+        (Dimension::Implicit | Dimension::Z, &[x, y, z]) => P::from_ordinates(x, y, Some(z), None),
+        (Dimension::M, &[x, y, m]) => P::from_ordinates(x, y, None, Some(m)),
+        (Dimension::Implicit | Dimension::ZM, &[x, y, z, m]) => P::from_ordinates(x, y, Some(z), Some(m)),
+        _ => return Err(invalid_format("unexpected number of ordinates")),
+    };
+
+    Ok(point)
+}